@@ -0,0 +1,49 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gray_matter::engine::{RAW, YAML};
+use gray_matter::Matter;
+
+const BODY: &str =
+    "# Title\n\nSome paragraph with *emphasis* and a [link](https://example.com).\n\n";
+
+fn with_front_matter() -> String {
+    format!(
+        "---\ntitle: Home\ntags:\n  - rust\n  - yaml\n---\n{}",
+        BODY.repeat(100)
+    )
+}
+
+fn with_large_front_matter() -> String {
+    format!(
+        "---\n{}---\n{}",
+        "description: Some front matter line\n".repeat(1000),
+        BODY
+    )
+}
+
+fn without_front_matter() -> String {
+    BODY.repeat(100)
+}
+
+fn parsing_benchmark(c: &mut Criterion) {
+    let matter: Matter<YAML> = Matter::new();
+    let input = with_front_matter();
+    c.bench_function("front matter", |b| {
+        b.iter(|| matter.parse(black_box(&input)))
+    });
+
+    // The raw engine leaves little besides handling the front matter itself to measure
+    let matter: Matter<RAW> = Matter::new();
+    let input = with_large_front_matter();
+    c.bench_function("large front matter", |b| {
+        b.iter(|| matter.parse(black_box(&input)))
+    });
+
+    let matter: Matter<YAML> = Matter::new();
+    let input = without_front_matter();
+    c.bench_function("no front matter", |b| {
+        b.iter(|| matter.parse(black_box(&input)))
+    });
+}
+
+criterion_group!(benches, parsing_benchmark);
+criterion_main!(benches);
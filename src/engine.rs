@@ -1,7 +1,16 @@
-use crate::Pod;
+use crate::{Error, Pod};
+use serde::{Deserialize, Deserializer};
 
+#[cfg(feature = "dotenv")]
+#[doc(hidden)]
+pub mod dotenv;
+#[cfg(feature = "hcl")]
+#[doc(hidden)]
+pub mod hcl;
 #[doc(hidden)]
 pub mod json;
+#[doc(hidden)]
+pub mod raw;
 #[cfg(feature = "toml")]
 #[doc(hidden)]
 pub mod toml;
@@ -9,19 +18,214 @@ pub mod toml;
 #[doc(hidden)]
 pub mod yaml;
 
+#[cfg(feature = "dotenv")]
+#[doc(inline)]
+pub use crate::engine::dotenv::DOTENV;
+#[cfg(feature = "hcl")]
+#[doc(inline)]
+pub use crate::engine::hcl::HCL;
 #[doc(inline)]
 pub use crate::engine::json::JSON;
+#[doc(inline)]
+pub use crate::engine::raw::RAW;
 #[cfg(feature = "toml")]
 #[doc(inline)]
 pub use crate::engine::toml::TOML;
 #[cfg(feature = "yaml")]
 #[doc(inline)]
-pub use crate::engine::yaml::YAML;
+pub use crate::engine::yaml::{UncoercedYAML, YAML};
+
+/// The delimiters enclosing the front matter handed to [`Engine::parse_with_context`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatterContext<'a> {
+    /// The opening delimiter, e.g. `---`.
+    pub delimiter: &'a str,
+    /// The closing delimiter, which is the opening one unless
+    /// [`Matter::close_delimiter`](crate::Matter::close_delimiter) is set.
+    pub close_delimiter: &'a str,
+    /// What to do with a key occurring twice in the same mapping, as set by
+    /// [`Matter::duplicate_keys`](crate::Matter::duplicate_keys).
+    pub duplicate_keys: DuplicateKeyPolicy,
+}
+
+/// What an engine does with a key occurring twice in the same mapping of the front matter.
+///
+/// Keys which end up the same once stringified, such as `1` and `"1"` in YAML, count as
+/// duplicates as well.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateKeyPolicy {
+    /// The last value wins.
+    #[default]
+    KeepLast,
+    /// The first value wins.
+    KeepFirst,
+    /// Parsing fails with [`Error::InvalidFrontMatter`](crate::Error::InvalidFrontMatter) listing
+    /// the duplicated keys.
+    Error,
+}
 
 /// The trait requirement used by [`Matter`](crate::Matter) when parsing the front matter.
 ///
 /// Implementing this trait in your own engine will allow you to create a custom front matter
-/// format that can be used by [gray_matter](crate).
+/// format that can be used by [gray_matter](crate). This is the only engine trait: the built-in
+/// engines implement it just like custom ones, and [`DynEngine`] follows from it.
+///
+/// Only [`parse`](Engine::parse) is required, which returns `Pod::Null` for content it cannot
+/// parse. Engines able to tell why implement [`try_parse`](Engine::try_parse) as well, and
+/// usually have `parse` fall back on it.
+///
+/// ## Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// # use gray_matter::engine::Engine;
+/// # use gray_matter::{Error, Matter, Pod};
+/// struct Lines;
+///
+/// impl Engine for Lines {
+///     fn parse(content: &str) -> Pod {
+///         Self::try_parse(content).unwrap_or(Pod::Null)
+///     }
+///
+///     fn try_parse(content: &str) -> Result<Pod, Error> {
+///         let mut data = Pod::new_hash();
+///         for line in content.lines() {
+///             let (key, value) = line.split_once('=').ok_or_else(|| {
+///                 Error::invalid_front_matter(format!("missing `=` in {:?}", line))
+///             })?;
+///             data[key.trim()] = Pod::String(value.trim().to_string());
+///         }
+///         Ok(data)
+///     }
+/// }
+///
+/// let matter: Matter<Lines> = Matter::new();
+/// let parsed_entity = matter.try_parse("---\ntitle = Home\n---\nOther stuff").unwrap();
+/// assert_eq!(parsed_entity.data.unwrap()["title"], Pod::String("Home".to_owned()));
+/// assert!(matter.try_parse("---\ntitle: Home\n---\nOther stuff").is_err());
+/// ```
 pub trait Engine {
     fn parse(content: &str) -> Pod;
+
+    /// Like [`parse`](Engine::parse), but fails with
+    /// [`Error::InvalidFrontMatter`](crate::Error::InvalidFrontMatter) describing why the content
+    /// could not be parsed, or [`Error::SyntaxError`](crate::Error::SyntaxError) if the engine
+    /// also knows where, rather than returning `Pod::Null`. Used by
+    /// [`Matter::try_parse`](crate::Matter::try_parse). Never fails by default.
+    fn try_parse(content: &str) -> Result<Pod, Error> {
+        Ok(Self::parse(content))
+    }
+
+    /// Like [`parse`](Engine::parse), but also receives the delimiters the front matter was
+    /// enclosed in, for formats whose dialect depends on them. This is what
+    /// [`Matter`](crate::Matter) calls, and it ignores the context by default.
+    fn parse_with_context(content: &str, ctx: &MatterContext) -> Pod {
+        let _ = ctx;
+        Self::parse(content)
+    }
+
+    /// Like [`try_parse`](Engine::try_parse), but also receives the context
+    /// [`parse_with_context`](Engine::parse_with_context) does. Ignores the context by default.
+    fn try_parse_with_context(content: &str, ctx: &MatterContext) -> Result<Pod, Error> {
+        let _ = ctx;
+        Self::try_parse(content)
+    }
+
+    /// A short, lowercase name of the format handled by the engine, e.g. `"yaml"`. It ends up in
+    /// [`ParsedEntity::format`](crate::ParsedEntity::format) whenever front matter is parsed.
+    fn format() -> Option<&'static str> {
+        None
+    }
+
+    /// Serializes `data` back into the format, with the keys of every hash sorted. Used by
+    /// [`Matter::reformat`](crate::Matter::reformat). Engines which cannot serialize fail with
+    /// [`Error::SerializeError`](crate::Error::SerializeError).
+    fn stringify(data: &Pod) -> Result<String, Error> {
+        let _ = data;
+        Err(Error::serialize_error(
+            "the engine does not support serialization".to_string(),
+        ))
+    }
+}
+
+/// An object-safe counterpart of [`Engine`], implemented for every engine.
+///
+/// This allows choosing an engine at runtime, e.g. with
+/// [`Matter::parse_with_engine`](crate::Matter::parse_with_engine).
+pub trait DynEngine {
+    fn parse(&self, content: &str) -> Pod;
+
+    fn try_parse(&self, content: &str) -> Result<Pod, Error> {
+        Ok(self.parse(content))
+    }
+
+    fn parse_with_context(&self, content: &str, ctx: &MatterContext) -> Pod {
+        let _ = ctx;
+        self.parse(content)
+    }
+
+    fn try_parse_with_context(&self, content: &str, ctx: &MatterContext) -> Result<Pod, Error> {
+        let _ = ctx;
+        self.try_parse(content)
+    }
+
+    fn format(&self) -> Option<&'static str>;
+
+    fn stringify(&self, data: &Pod) -> Result<String, Error>;
+}
+
+impl<T: Engine> DynEngine for T {
+    fn parse(&self, content: &str) -> Pod {
+        T::parse(content)
+    }
+
+    fn try_parse(&self, content: &str) -> Result<Pod, Error> {
+        T::try_parse(content)
+    }
+
+    fn parse_with_context(&self, content: &str, ctx: &MatterContext) -> Pod {
+        T::parse_with_context(content, ctx)
+    }
+
+    fn try_parse_with_context(&self, content: &str, ctx: &MatterContext) -> Result<Pod, Error> {
+        T::try_parse_with_context(content, ctx)
+    }
+
+    fn format(&self) -> Option<&'static str> {
+        T::format()
+    }
+
+    fn stringify(&self, data: &Pod) -> Result<String, Error> {
+        T::stringify(data)
+    }
+}
+
+/// Reads a `Pod` out of any [`Deserializer`](serde::Deserializer), which turns an engine for a
+/// format supported by serde into little more than a call to this function.
+///
+/// Fails with the error of the deserializer itself, which may tell where the content is invalid,
+/// as the line and column of `serde_json::Error` do.
+///
+/// ## Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// # extern crate json as serde_json;
+/// # use gray_matter::engine::{parse_via_serde, Engine};
+/// # use gray_matter::Pod;
+/// struct MyJSON;
+///
+/// impl Engine for MyJSON {
+///     fn parse(content: &str) -> Pod {
+///         let mut deserializer = serde_json::Deserializer::from_str(content);
+///         parse_via_serde(&mut deserializer).unwrap_or(Pod::Null)
+///     }
+/// }
+///
+/// assert_eq!(MyJSON::parse("[1]"), Pod::Array(vec![Pod::Integer(1)]));
+/// ```
+pub fn parse_via_serde<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pod, D::Error> {
+    <Pod as Deserialize>::deserialize(deserializer)
 }
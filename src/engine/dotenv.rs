@@ -0,0 +1,111 @@
+use crate::engine::Engine;
+use crate::Pod;
+use std::collections::HashMap;
+
+/// [`Engine`](crate::engine::Engine) for [dotenv](https://github.com/motdotla/dotenv) style
+/// `KEY=VALUE` lines.
+///
+/// Every value is kept as `Pod::String`, since dotenv has no other types. Lines starting with `#`
+/// are comments, and values may be wrapped in single or double quotes.
+pub struct DOTENV;
+
+impl Engine for DOTENV {
+    fn parse(content: &str) -> Pod {
+        let mut hash = HashMap::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => (key.trim(), value.trim()),
+                _ => return Pod::Null,
+            };
+            let value = match parse_value(value) {
+                Some(value) => value,
+                None => return Pod::Null,
+            };
+            hash.insert(key.to_string(), Pod::String(value));
+        }
+        Pod::Hash(hash)
+    }
+
+    fn format() -> Option<&'static str> {
+        Some("dotenv")
+    }
+}
+
+/// Unquotes a value. Double quoted values support `\n`, `\"` and `\\` escapes, single quoted
+/// values are taken literally and unquoted values end at an inline ` #` comment.
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(quoted) = value.strip_prefix('\'') {
+        return Some(quoted[..quoted.find('\'')?].to_string());
+    }
+    if let Some(quoted) = value.strip_prefix('"') {
+        let mut unquoted = String::new();
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Some(unquoted),
+                '\\' => match chars.next()? {
+                    'n' => unquoted.push('\n'),
+                    escaped => unquoted.push(escaped),
+                },
+                _ => unquoted.push(c),
+            }
+        }
+        return None;
+    }
+    let value = match value.find(" #") {
+        Some(i) => &value[..i],
+        None => value,
+    };
+    Some(value.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::dotenv::DOTENV;
+    use crate::matter::Matter;
+    use crate::Pod;
+
+    #[test]
+    fn test_matter() {
+        let matter: Matter<DOTENV> = Matter::new();
+        let input = "---\nAPI_URL=https://x\nDEBUG=true\n---";
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data.len(), 2);
+        assert_eq!(data["API_URL"], Pod::String("https://x".to_string()));
+        assert_eq!(data["DEBUG"], Pod::String("true".to_string()));
+    }
+
+    #[test]
+    fn comments_and_quotes() {
+        let matter: Matter<DOTENV> = Matter::new();
+        let input = r#"---
+# a comment
+export NAME="gray \"matter\"\nrs"
+SINGLE='#not a comment'
+PLAIN = value # a comment
+EMPTY=
+---"#;
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data.len(), 4);
+        assert_eq!(data["NAME"], Pod::String("gray \"matter\"\nrs".to_string()));
+        assert_eq!(data["SINGLE"], Pod::String("#not a comment".to_string()));
+        assert_eq!(data["PLAIN"], Pod::String("value".to_string()));
+        assert_eq!(data["EMPTY"], Pod::String("".to_string()));
+
+        let result = matter.parse("---\nNOT A PAIR\n---");
+        assert!(
+            result.data.is_none(),
+            "should get no data out of a line without `=`"
+        );
+        let result = matter.parse("---\nOPEN=\"unterminated\n---");
+        assert!(
+            result.data.is_none(),
+            "should get no data out of an unterminated quote"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::engine::Engine;
+use crate::Pod;
+use hcl::Value;
+use std::collections::HashMap;
+
+/// [`Engine`](crate::engine::Engine) for the [HCL](https://github.com/hashicorp/hcl) configuration
+/// format.
+///
+/// Attributes become scalars, and blocks become nested hashes keyed by their identifier and labels.
+pub struct HCL;
+
+impl Engine for HCL {
+    fn parse(content: &str) -> Pod {
+        match hcl::from_str::<Value>(content) {
+            Ok(value) => value.into(),
+            Err(_) => Pod::Null,
+        }
+    }
+
+    fn format() -> Option<&'static str> {
+        Some("hcl")
+    }
+}
+
+impl From<Value> for Pod {
+    fn from(hcl_value: Value) -> Self {
+        match hcl_value {
+            Value::Null => Pod::Null,
+            Value::Bool(val) => Pod::Boolean(val),
+            Value::Number(val) => {
+                if let Some(int) = val.as_i64() {
+                    Pod::Integer(int)
+                } else if val.is_u64() {
+                    Pod::String(val.to_string())
+                } else {
+                    Pod::Float(val.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            Value::String(val) => Pod::String(val),
+            Value::Array(val) => val
+                .iter()
+                .map(|elem| elem.into())
+                .collect::<Vec<Pod>>()
+                .into(),
+            Value::Object(val) => val
+                .iter()
+                .map(|(key, elem)| (key.to_owned(), elem.into()))
+                .collect::<HashMap<String, Pod>>()
+                .into(),
+        }
+    }
+}
+
+impl From<&Value> for Pod {
+    fn from(val: &Value) -> Self {
+        val.to_owned().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::hcl::HCL;
+    use crate::entity::ParsedEntityStruct;
+    use crate::matter::Matter;
+    use crate::Pod;
+    use serde::Deserialize;
+
+    #[test]
+    fn test_matter() {
+        let matter: Matter<HCL> = Matter::new();
+        let input = r#"---
+title = "x"
+tags = ["a","b"]
+---"#;
+        #[derive(Deserialize, PartialEq, Debug)]
+        struct FrontMatter {
+            title: String,
+            tags: Vec<String>,
+        }
+        let data_expected = FrontMatter {
+            title: "x".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+        };
+        let result: ParsedEntityStruct<FrontMatter> = matter.parse_with_struct(input).unwrap();
+        assert_eq!(result.data, data_expected);
+        assert_eq!(result.format, Some("hcl"));
+    }
+
+    #[test]
+    fn blocks() {
+        let matter: Matter<HCL> = Matter::new();
+        let input = r#"---
+weight = 2
+ratio = 0.5
+author "me" {
+  email = "me@example.com"
+}
+---"#;
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["weight"], Pod::Integer(2));
+        assert_eq!(data["ratio"], Pod::Float(0.5));
+        assert_eq!(
+            data["author"]["me"]["email"],
+            Pod::String("me@example.com".to_string())
+        );
+    }
+}
@@ -1,17 +1,44 @@
-use crate::engine::Engine;
-use crate::Pod;
+use crate::engine::{parse_via_serde, Engine};
+use crate::{Error, Pod};
 use json::Value;
 use std::collections::HashMap;
 
 /// [`Engine`](crate::engine::Engine) for the [JSON](https://www.json.org/) configuration format.
+///
+/// Integers too large for `Pod::Integer` are kept as a `Pod::String` of their digits, rather than
+/// being rounded into a `Pod::Float`.
 pub struct JSON;
 
 impl Engine for JSON {
     fn parse(content: &str) -> Pod {
-        match content.parse::<Value>() {
-            Ok(data) => data.into(),
-            Err(_) => Pod::Null,
-        }
+        Self::try_parse(content).unwrap_or(Pod::Null)
+    }
+
+    fn try_parse(content: &str) -> Result<Pod, Error> {
+        let mut deserializer = json::Deserializer::from_str(content);
+        let data =
+            parse_via_serde(&mut deserializer).and_then(|data| deserializer.end().map(|()| data));
+        data.map_err(|err| {
+            let (line, column) = (err.line(), err.column());
+            let msg = err.to_string();
+            match msg.strip_suffix(&format!(" at line {line} column {column}")) {
+                Some(msg) if line > 0 => Error::SyntaxError {
+                    msg: msg.to_string(),
+                    line,
+                    column,
+                },
+                _ => Error::invalid_front_matter(msg),
+            }
+        })
+    }
+
+    fn format() -> Option<&'static str> {
+        Some("json")
+    }
+
+    fn stringify(data: &Pod) -> Result<String, Error> {
+        let value: Value = data.clone().into();
+        Ok(json::to_string_pretty(&value)?)
     }
 }
 
@@ -23,6 +50,8 @@ impl From<Value> for Pod {
             Value::Number(val) => {
                 if let Some(int) = val.as_i64() {
                     Pod::Integer(int)
+                } else if val.is_u64() {
+                    Pod::String(val.to_string())
                 } else {
                     // NOTE: Looking at the source of serde_json, it looks like `as_f64` will
                     // always be Some. https://docs.rs/serde_json/latest/src/serde_json/number.rs.html#240-249
@@ -53,8 +82,10 @@ impl From<&Value> for Pod {
 #[cfg(test)]
 mod test {
     use crate::engine::json::JSON;
+    use crate::engine::Engine;
     use crate::entity::ParsedEntityStruct;
     use crate::matter::Matter;
+    use crate::{Error, Pod};
     use serde::Deserialize;
 
     #[test]
@@ -81,4 +112,51 @@ Other stuff"#;
         let result: ParsedEntityStruct<FrontMatter> = matter.parse_with_struct(input).unwrap();
         assert_eq!(result.data, data_expected);
     }
+
+    #[test]
+    fn parse_values() {
+        let data =
+            JSON::parse(r#"{"big": 18446744073709551615, "list": [1, -2, 0.5, null, true]}"#);
+        assert_eq!(data["big"], Pod::String("18446744073709551615".to_string()));
+        assert_eq!(
+            data["list"],
+            Pod::Array(vec![
+                Pod::Integer(1),
+                Pod::Integer(-2),
+                Pod::Float(0.5),
+                Pod::Null,
+                Pod::Boolean(true)
+            ])
+        );
+        assert_eq!(JSON::parse(r#"{"a": 1} trailing"#), Pod::Null);
+
+        let value: json::Value =
+            json::from_str("[10000000000000000000, 9223372036854775807]").unwrap();
+        assert_eq!(
+            Pod::from_json(&value),
+            Pod::Array(vec![
+                Pod::String("10000000000000000000".to_string()),
+                Pod::Integer(i64::MAX)
+            ])
+        );
+    }
+
+    #[test]
+    fn syntax_error() {
+        let matter: Matter<JSON> = Matter::new();
+        let input = "---\n{\n  \"title\": \"JSON\",\n  \"tags\": [\"a\" \"b\"]\n}\n---\nContent";
+        assert!(matter.parse(input).data.is_none());
+        assert_eq!(
+            matter.try_parse(input),
+            Err(Error::SyntaxError {
+                msg: "expected `,` or `]`".to_string(),
+                line: 3,
+                column: 16,
+            })
+        );
+        match JSON::try_parse("{} {}") {
+            Err(Error::SyntaxError { line, column, .. }) => assert_eq!((line, column), (1, 4)),
+            result => panic!("should fail on the trailing characters, got {:?}", result),
+        }
+    }
 }
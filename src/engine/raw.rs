@@ -0,0 +1,39 @@
+use crate::engine::Engine;
+use crate::{Error, Pod};
+
+/// [`Engine`](crate::engine::Engine) which does not parse the front matter at all, but keeps it as
+/// a single `Pod::String`.
+pub struct RAW;
+
+impl Engine for RAW {
+    fn parse(content: &str) -> Pod {
+        Pod::String(content.to_owned())
+    }
+
+    fn stringify(data: &Pod) -> Result<String, Error> {
+        data.as_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::engine::raw::RAW;
+    use crate::matter::Matter;
+    use crate::Pod;
+
+    #[test]
+    fn test_matter() {
+        let matter: Matter<RAW> = Matter::new();
+        let input = "---\ntitle: RAW\ntags = [not, parsed]\n---\nOther stuff";
+        let result = matter.parse(input);
+        assert_eq!(
+            result.data,
+            Some(Pod::String("title: RAW\ntags = [not, parsed]".to_string()))
+        );
+        assert_eq!(result.content, "Other stuff");
+        assert_eq!(
+            matter.reformat(input).unwrap(),
+            "---\ntitle: RAW\ntags = [not, parsed]\n---\n\nOther stuff\n"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::engine::Engine;
-use crate::Pod;
+use crate::value::pod::base64_encode;
+use crate::{Error, Pod};
 use std::collections::HashMap;
 use toml::Value;
 
@@ -8,11 +9,55 @@ pub struct TOML;
 
 impl Engine for TOML {
     fn parse(content: &str) -> Pod {
-        match toml::from_str::<Value>(content) {
-            Ok(value) => value.into(),
-            Err(_) => Pod::Null,
-        }
+        Self::try_parse(content).unwrap_or(Pod::Null)
+    }
+
+    fn try_parse(content: &str) -> Result<Pod, Error> {
+        toml::from_str::<Value>(content)
+            .map(Pod::from)
+            .map_err(|err| {
+                let msg = err.to_string();
+                // `line_col` is zero based, while the message ends with the one based position
+                match err.line_col().map(|(line, col)| (line + 1, col + 1)) {
+                    Some((line, column)) => Error::SyntaxError {
+                        msg: msg
+                            .strip_suffix(&format!(" at line {line} column {column}"))
+                            .unwrap_or(&msg)
+                            .to_string(),
+                        line,
+                        column,
+                    },
+                    None => Error::invalid_front_matter(msg),
+                }
+            })
+    }
+
+    fn format() -> Option<&'static str> {
+        Some("toml")
     }
+
+    fn stringify(data: &Pod) -> Result<String, Error> {
+        toml::to_string(&to_toml(data)?).map_err(|err| Error::serialize_error(err.to_string()))
+    }
+}
+
+/// Converts a `Pod` into a TOML value. Null values are left out of tables, as TOML has no null.
+fn to_toml(pod: &Pod) -> Result<Value, Error> {
+    Ok(match *pod {
+        Pod::Null => return Err(Error::serialize_error("TOML has no null value".to_string())),
+        Pod::String(ref val) => Value::String(val.clone()),
+        Pod::Integer(val) => Value::Integer(val),
+        Pod::Float(val) => Value::Float(val),
+        Pod::Boolean(val) => Value::Boolean(val),
+        Pod::Array(ref val) => Value::Array(val.iter().map(to_toml).collect::<Result<_, _>>()?),
+        Pod::Hash(ref val) => Value::Table(
+            val.iter()
+                .filter(|(_, elem)| !elem.is_null())
+                .map(|(key, elem)| Ok((key.clone(), to_toml(elem)?)))
+                .collect::<Result<_, Error>>()?,
+        ),
+        Pod::Bytes(ref val) => Value::String(base64_encode(val)),
+    })
 }
 
 impl From<Value> for Pod {
@@ -46,8 +91,10 @@ impl From<&Value> for Pod {
 #[cfg(test)]
 mod test {
     use crate::engine::toml::TOML;
+    use crate::engine::DuplicateKeyPolicy;
     use crate::entity::ParsedEntityStruct;
     use crate::matter::Matter;
+    use crate::Error;
     use serde::Deserialize;
 
     #[test]
@@ -75,4 +122,38 @@ categories = "front matter toml"
         let result: ParsedEntityStruct<FrontMatter> = matter.parse_with_struct(input).unwrap();
         assert_eq!(result.data, data_expected);
     }
+
+    #[test]
+    fn syntax_error() {
+        let matter: Matter<TOML> = Matter::new();
+        let input = "---\ntitle = \"TOML\"\ndescription \"Front matter\"\n---\nContent";
+        assert!(matter.parse(input).data.is_none());
+        match matter.try_parse(input) {
+            Err(Error::SyntaxError { msg, line, column }) => {
+                assert_eq!((line, column), (2, 13));
+                assert!(!msg.contains("at line"), "{}", msg);
+            }
+            result => panic!("should fail with the syntax error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn duplicate_keys_always_rejected() {
+        let mut matter: Matter<TOML> = Matter::new();
+        let input = "---\ntitle = \"first\"\ntitle = \"second\"\n---\nContent";
+        // TOML forbids duplicate keys, whatever the policy
+        for policy in [
+            DuplicateKeyPolicy::KeepLast,
+            DuplicateKeyPolicy::KeepFirst,
+            DuplicateKeyPolicy::Error,
+        ] {
+            matter.duplicate_keys = policy;
+            match matter.try_parse(input) {
+                Err(Error::SyntaxError { msg, .. }) => {
+                    assert!(msg.contains("duplicate key"), "{}", msg);
+                }
+                result => panic!("should reject the duplicate key, got {:?}", result),
+            }
+        }
+    }
 }
@@ -1,22 +1,283 @@
-use crate::engine::Engine;
-use crate::Pod;
+use crate::engine::{DuplicateKeyPolicy, Engine, MatterContext};
+use crate::value::pod::base64_encode;
+use crate::{Error, Pod};
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use yaml::{Yaml, YamlLoader};
+use yaml::parser::{MarkedEventReceiver, Parser, Tag};
+use yaml::scanner::{Marker, TScalarStyle};
+use yaml::{Event, Yaml, YamlEmitter};
 
 /// [`Engine`](crate::engine::Engine) for the [YAML](https://yaml.org) configuration format.
 pub struct YAML;
 
 impl Engine for YAML {
     fn parse(content: &str) -> Pod {
-        match YamlLoader::load_from_str(content) {
-            Ok(docs) => {
-                let mut doc = Pod::Null;
-                if !docs.is_empty() {
-                    doc = docs[0].clone().into();
+        Self::try_parse(content).unwrap_or(Pod::Null)
+    }
+
+    fn try_parse(content: &str) -> Result<Pod, Error> {
+        load(content, true, DuplicateKeyPolicy::default())
+    }
+
+    fn parse_with_context(content: &str, ctx: &MatterContext) -> Pod {
+        Self::try_parse_with_context(content, ctx).unwrap_or(Pod::Null)
+    }
+
+    fn try_parse_with_context(content: &str, ctx: &MatterContext) -> Result<Pod, Error> {
+        load(content, true, ctx.duplicate_keys)
+    }
+
+    fn format() -> Option<&'static str> {
+        Some("yaml")
+    }
+
+    fn stringify(data: &Pod) -> Result<String, Error> {
+        let mut out = String::new();
+        YamlEmitter::new(&mut out)
+            .dump(&to_yaml(data))
+            .map_err(|err| Error::serialize_error(format!("{err:?}")))?;
+        Ok(out.trim_start_matches("---").trim_start().to_string())
+    }
+}
+
+/// Converts a `Pod` into a YAML node, inserting the keys of every hash in sorted order.
+fn to_yaml(pod: &Pod) -> Yaml {
+    match *pod {
+        Pod::Null => Yaml::Null,
+        Pod::String(ref val) => Yaml::String(val.clone()),
+        Pod::Integer(val) => Yaml::Integer(val),
+        Pod::Float(val) if val.is_nan() => Yaml::Real(".nan".to_string()),
+        Pod::Float(val) if val.is_infinite() => {
+            Yaml::Real(if val > 0.0 { ".inf" } else { "-.inf" }.to_string())
+        }
+        Pod::Float(val) => Yaml::Real(format!("{val:?}")),
+        Pod::Boolean(val) => Yaml::Boolean(val),
+        Pod::Array(ref val) => Yaml::Array(val.iter().map(to_yaml).collect()),
+        Pod::Hash(_) => Yaml::Hash(
+            pod.sorted_entries()
+                .into_iter()
+                .map(|(key, elem)| (Yaml::String(key.to_string()), to_yaml(elem)))
+                .collect(),
+        ),
+        Pod::Bytes(ref val) => Yaml::String(base64_encode(val)),
+    }
+}
+
+/// [`Engine`](crate::engine::Engine) for the [YAML](https://yaml.org) configuration format, which
+/// keeps every scalar as the literal text written by the author.
+///
+/// Unlike [`YAML`], values such as `true`, `42` or `1.10` all end up as `Pod::String`. A value
+/// left empty, like `key:`, is an empty `Pod::String`.
+pub struct UncoercedYAML;
+
+impl Engine for UncoercedYAML {
+    fn parse(content: &str) -> Pod {
+        Self::try_parse(content).unwrap_or(Pod::Null)
+    }
+
+    fn try_parse(content: &str) -> Result<Pod, Error> {
+        load(content, false, DuplicateKeyPolicy::default())
+    }
+
+    fn parse_with_context(content: &str, ctx: &MatterContext) -> Pod {
+        Self::try_parse_with_context(content, ctx).unwrap_or(Pod::Null)
+    }
+
+    fn try_parse_with_context(content: &str, ctx: &MatterContext) -> Result<Pod, Error> {
+        load(content, false, ctx.duplicate_keys)
+    }
+
+    fn format() -> Option<&'static str> {
+        Some("yaml")
+    }
+
+    fn stringify(data: &Pod) -> Result<String, Error> {
+        YAML::stringify(data)
+    }
+}
+
+/// Parses the first YAML document in `content`, resolving the type of plain scalars if `coerce`
+/// is set, and treating duplicate keys according to `duplicate_keys`.
+fn load(content: &str, coerce: bool, duplicate_keys: DuplicateKeyPolicy) -> Result<Pod, Error> {
+    let mut builder = PodBuilder {
+        source: content,
+        coerce,
+        duplicate_keys,
+        doc: None,
+        stack: vec![],
+        anchors: HashMap::new(),
+        duplicates: vec![],
+        tilde: None,
+    };
+    Parser::new_from_str(content)
+        .load(&mut builder, false)
+        // The message ends with the position of the error
+        .map_err(|err| Error::invalid_front_matter(err.to_string()))?;
+    builder.settle_tilde(None);
+    if !builder.duplicates.is_empty() {
+        return Err(Error::invalid_front_matter(format!(
+            "duplicate keys: {}",
+            builder.duplicates.join(", ")
+        )));
+    }
+    Ok(builder.doc.unwrap_or(Pod::Null))
+}
+
+/// Resolves the type of a scalar the way [`YamlLoader`](yaml::YamlLoader) does.
+fn resolve_scalar(value: String, style: TScalarStyle, tag: Option<Tag>) -> Yaml {
+    if style != TScalarStyle::Plain {
+        return Yaml::String(value);
+    }
+    match tag {
+        Some(Tag { handle, suffix }) if handle == "tag:yaml.org,2002:" => match suffix.as_str() {
+            "bool" => value.parse().map_or(Yaml::BadValue, Yaml::Boolean),
+            "int" => value.parse().map_or(Yaml::BadValue, Yaml::Integer),
+            "float" => match Yaml::from_str(&value) {
+                Yaml::Integer(_) | Yaml::Real(_) => Yaml::Real(value),
+                _ => Yaml::BadValue,
+            },
+            "null" => match value.as_str() {
+                "~" | "null" => Yaml::Null,
+                _ => Yaml::BadValue,
+            },
+            _ => Yaml::String(value),
+        },
+        Some(_) => Yaml::String(value),
+        None => Yaml::from_str(&value),
+    }
+}
+
+/// The text of a YAML node used as a key, or `None` for nodes which cannot be keys.
+fn key_of(node: &Yaml) -> Option<String> {
+    match node {
+        Yaml::String(s) | Yaml::Real(s) => Some(s.to_string()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Null => Some("null".to_string()),
+        // Other types should not be expressible as keys.
+        _ => None,
+    }
+}
+
+/// A collection under construction, along with its anchor id. Mappings hold the key awaiting
+/// its value, which is `Some(None)` for a key that cannot be one.
+enum Frame {
+    Sequence(Vec<Pod>, usize),
+    Mapping(HashMap<String, Pod>, Option<Option<String>>, usize),
+}
+
+/// Builds a `Pod` out of YAML events.
+struct PodBuilder<'a> {
+    source: &'a str,
+    coerce: bool,
+    duplicate_keys: DuplicateKeyPolicy,
+    doc: Option<Pod>,
+    stack: Vec<Frame>,
+    anchors: HashMap<usize, Pod>,
+    duplicates: Vec<String>,
+    /// The anchor and position of a `~` scalar which may be an empty one, when not coercing.
+    tilde: Option<(usize, usize)>,
+}
+
+impl PodBuilder<'_> {
+    fn insert(&mut self, node: Pod, key: Option<String>, anchor: usize) {
+        if anchor > 0 {
+            self.anchors.insert(anchor, node.clone());
+        }
+        match self.stack.last_mut() {
+            Some(Frame::Sequence(vec, _)) => vec.push(node),
+            Some(Frame::Mapping(_, pending @ None, _)) => *pending = Some(key),
+            Some(Frame::Mapping(hash, pending, _)) => {
+                let key = match pending.take().unwrap() {
+                    Some(key) => key,
+                    None => return,
+                };
+                match hash.entry(key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(node);
+                    }
+                    Entry::Occupied(mut entry) => match self.duplicate_keys {
+                        DuplicateKeyPolicy::KeepLast => {
+                            entry.insert(node);
+                        }
+                        DuplicateKeyPolicy::KeepFirst => {}
+                        DuplicateKeyPolicy::Error => {
+                            if !self.duplicates.contains(entry.key()) {
+                                self.duplicates.push(entry.key().clone());
+                            }
+                        }
+                    },
                 }
-                doc
             }
-            Err(..) => Pod::Null,
+            None => {
+                self.doc.get_or_insert(node);
+            }
+        }
+    }
+
+    /// Inserts a collection. Used as a key, it is stringified when scalars are not coerced, and
+    /// dropped like [`YamlLoader`](yaml::YamlLoader) does otherwise.
+    fn insert_collection(&mut self, node: Pod, anchor: usize) {
+        let key = Some(node.as_string_lossy()).filter(|_| !self.coerce);
+        self.insert(node, key, anchor);
+    }
+
+    /// Inserts the pending `~` scalar, given the position of the event after it.
+    ///
+    /// The parser reports an empty scalar as `~`, at the position of the token after it. So a `~`
+    /// is only the author's when the source has one there, and the next event comes after it.
+    fn settle_tilde(&mut self, next: Option<usize>) {
+        if let Some((anchor, at)) = self.tilde.take() {
+            let value = if next == Some(at) { "" } else { "~" };
+            self.insert(
+                Pod::String(value.to_string()),
+                Some(value.to_string()),
+                anchor,
+            );
+        }
+    }
+}
+
+impl MarkedEventReceiver for PodBuilder<'_> {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        self.settle_tilde(Some(mark.index()));
+        match ev {
+            Event::Scalar(value, style, anchor, tag) => {
+                let node = if self.coerce {
+                    resolve_scalar(value, style, tag)
+                } else if value == "~" && style == TScalarStyle::Plain {
+                    // The marker counts characters
+                    if self.source.chars().nth(mark.index()) == Some('~') {
+                        self.tilde = Some((anchor, mark.index()));
+                        return;
+                    }
+                    Yaml::String(String::new())
+                } else {
+                    Yaml::String(value)
+                };
+                let key = key_of(&node);
+                self.insert(node.into(), key, anchor);
+            }
+            Event::Alias(anchor) => {
+                let node = self.anchors.get(&anchor).cloned().unwrap_or(Pod::Null);
+                let key = Some(node.as_string_lossy());
+                self.insert(node, key, 0);
+            }
+            Event::SequenceStart(anchor, _) => self.stack.push(Frame::Sequence(vec![], anchor)),
+            Event::MappingStart(anchor, _) => {
+                self.stack
+                    .push(Frame::Mapping(HashMap::new(), None, anchor))
+            }
+            Event::SequenceEnd | Event::MappingEnd => match self.stack.pop() {
+                Some(Frame::Sequence(vec, anchor)) => {
+                    self.insert_collection(Pod::Array(vec), anchor)
+                }
+                Some(Frame::Mapping(hash, _, anchor)) => {
+                    self.insert_collection(Pod::Hash(hash), anchor)
+                }
+                None => {}
+            },
+            _ => {}
         }
     }
 }
@@ -63,9 +324,11 @@ impl From<&Yaml> for Pod {
 
 #[cfg(test)]
 mod test {
-    use crate::engine::yaml::YAML;
+    use crate::engine::yaml::{UncoercedYAML, YAML};
+    use crate::engine::{DuplicateKeyPolicy, Engine};
     use crate::entity::ParsedEntityStruct;
     use crate::matter::Matter;
+    use crate::{Error, Pod};
     use serde::Deserialize;
 
     #[test]
@@ -118,4 +381,150 @@ null: boo
         let result: ParsedEntityStruct<FrontMatter> = matter.parse_with_struct(input).unwrap();
         assert_eq!(result.data, data_expected);
     }
+
+    #[test]
+    fn uncoerced_scalars() {
+        let matter: Matter<UncoercedYAML> = Matter::new();
+        let input = r#"---
+version: 1.10
+draft: true
+weight: 042
+title: "quoted"
+tags:
+  - &tag 1
+  - *tag
+nested:
+  empty:
+---"#;
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["version"], Pod::String("1.10".to_string()));
+        assert_eq!(data["draft"], Pod::String("true".to_string()));
+        assert_eq!(data["weight"], Pod::String("042".to_string()));
+        assert_eq!(data["title"], Pod::String("quoted".to_string()));
+        assert_eq!(
+            data["tags"],
+            Pod::Array(vec![
+                Pod::String("1".to_string()),
+                Pod::String("1".to_string())
+            ])
+        );
+        assert_eq!(data["nested"]["empty"], Pod::String("".to_string()));
+
+        let matter: Matter<YAML> = Matter::new();
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["version"], Pod::Float(1.1));
+    }
+
+    #[test]
+    fn uncoerced_empty_and_tilde() {
+        let matter: Matter<UncoercedYAML> = Matter::new();
+        let input = "---\ntitle: ~\nempty:\n~: tilde key\nafter:\nété: ~\nlist: [~, a]\nlast:\n---";
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["title"], Pod::String("~".to_string()));
+        assert_eq!(data["empty"], Pod::String("".to_string()));
+        assert_eq!(data["~"], Pod::String("tilde key".to_string()));
+        assert_eq!(data["after"], Pod::String("".to_string()));
+        assert_eq!(data["été"], Pod::String("~".to_string()));
+        assert_eq!(
+            data["list"],
+            Pod::Array(vec![
+                Pod::String("~".to_string()),
+                Pod::String("a".to_string())
+            ])
+        );
+        assert_eq!(data["last"], Pod::String("".to_string()));
+        assert_eq!(data.len(), 7);
+    }
+
+    #[test]
+    fn anchors_and_aliases() {
+        let matter: Matter<YAML> = Matter::new();
+        let input = r#"---
+defaults: &defaults
+  layout: post
+  tags: &tags [rust, yaml]
+  draft: false
+post: *defaults
+tags: *tags
+single: &one 1
+again: *one
+---"#;
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["post"], data["defaults"]);
+        assert_eq!(data["post"]["layout"], Pod::String("post".to_string()));
+        assert_eq!(data["post"]["draft"], Pod::Boolean(false));
+        assert_eq!(
+            data["tags"],
+            Pod::Array(vec![
+                Pod::String("rust".to_string()),
+                Pod::String("yaml".to_string())
+            ])
+        );
+        assert_eq!(data["post"]["tags"], data["tags"]);
+        assert_eq!(data["again"], Pod::Integer(1));
+    }
+
+    #[test]
+    fn deterministic_stringify() {
+        let mut forward = Pod::new_hash();
+        let mut backward = Pod::new_hash();
+        let keys = ["title", "date", "author", "tags", "draft", "weight"];
+        for (i, key) in keys.iter().enumerate() {
+            forward[*key] = Pod::Integer(i as i64);
+        }
+        for (i, key) in keys.iter().enumerate().rev() {
+            backward[*key] = Pod::Integer(i as i64);
+        }
+        let expected = "author: 2\ndate: 1\ndraft: 4\ntags: 3\ntitle: 0\nweight: 5";
+        for _ in 0..10 {
+            assert_eq!(YAML::stringify(&forward.clone()).unwrap(), expected);
+            assert_eq!(YAML::stringify(&backward.clone()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn syntax_error() {
+        let matter: Matter<YAML> = Matter::new();
+        let input = "---\ntitle: YAML\ntags: [rust, yaml\n---\nContent";
+        assert!(matter.parse(input).data.is_none());
+        match matter.try_parse(input) {
+            Err(Error::InvalidFrontMatter(msg)) => {
+                assert!(msg.contains("expected ',' or ']'"), "{}", msg)
+            }
+            result => panic!("should fail with the syntax error, got {:?}", result),
+        }
+        assert!(UncoercedYAML::try_parse("tags: [rust, yaml").is_err());
+        assert_eq!(YAML::try_parse(""), Ok(Pod::Null));
+    }
+
+    #[test]
+    fn duplicate_keys() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "---\ntitle: first\ntags: [a]\ntitle: second\n1: one\n\"1\": two\n---\n";
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["title"], Pod::String("second".to_string()));
+        assert_eq!(data["1"], Pod::String("two".to_string()));
+
+        matter.duplicate_keys = DuplicateKeyPolicy::KeepFirst;
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["title"], Pod::String("first".to_string()));
+        assert_eq!(data["1"], Pod::String("one".to_string()));
+
+        matter.duplicate_keys = DuplicateKeyPolicy::Error;
+        assert!(matter.parse(input).data.is_none());
+        assert_eq!(
+            matter.try_parse(input),
+            Err(Error::InvalidFrontMatter(
+                "duplicate keys: title, 1".to_string()
+            ))
+        );
+        let nested = "---\nouter:\n  inner: 1\n  inner: 2\n---\n";
+        assert!(matter.try_parse(nested).is_err());
+        assert!(matter.try_parse("---\ntitle: first\n---\n").is_ok());
+
+        let mut matter: Matter<UncoercedYAML> = Matter::new();
+        matter.duplicate_keys = DuplicateKeyPolicy::KeepFirst;
+        let data = matter.parse(input).data.unwrap();
+        assert_eq!(data["title"], Pod::String("first".to_string()));
+    }
 }
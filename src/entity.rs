@@ -1,4 +1,14 @@
 use crate::Pod;
+use std::ops::Range;
+
+/// Details on an excerpt found by [`Matter`](crate::Matter), beyond its text and its
+/// [`excerpt_span`](crate::ParsedEntity::excerpt_span).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ExcerptInfo {
+    /// Whether the excerpt was delimited by the front matter delimiter, for lack of an
+    /// [`excerpt_delimiter`](crate::Matter::excerpt_delimiter), rather than by an explicit one.
+    pub delimiter_fallback: bool,
+}
 
 /// `ParsedEntity` stores a parsed result.
 ///
@@ -25,17 +35,66 @@ use crate::Pod;
 /// ```
 #[derive(PartialEq, Debug)]
 pub struct ParsedEntity {
-    /// [`Some(Pod)`](crate::Pod) if front matter was found. `None` otherwise.
+    /// [`Some(Pod)`](crate::Pod) if front matter was found. `None` otherwise, including front
+    /// matter which parses to nothing but null or an empty hash, e.g. when it only holds comments.
     pub data: Option<Pod>,
     /// The full input, but with the front matter and delimiters stripped out. Any excerpt is also
     /// part of this field.
     pub content: String,
     /// A string containing the excerpt, if found. `None` otherwise.
     pub excerpt: Option<String>,
-    /// The original input.
+    /// The byte range of the excerpt within `orig`, if found. `None` otherwise.
+    pub excerpt_span: Option<Range<usize>>,
+    /// How the excerpt was delimited, if found. `None` otherwise, like
+    /// [`excerpt_span`](ParsedEntity::excerpt_span).
+    pub excerpt_info: Option<ExcerptInfo>,
+    /// Whether `content` was cut off at [`Matter::max_content_bytes`](crate::Matter::max_content_bytes).
+    pub content_truncated: bool,
+    /// The original input, or an empty string if [`Matter::keep_orig`](crate::Matter::keep_orig)
+    /// is off.
     pub orig: String,
     /// The raw front matter. Empty string if no front matter is found.
     pub matter: String,
+    /// The [format](crate::engine::Engine::format) the front matter was parsed as. `None` if no
+    /// front matter is found, or if the engine does not report one.
+    pub format: Option<&'static str>,
+}
+
+impl ParsedEntity {
+    /// Transforms the front matter with `f`, keeping every other field. Returns `None` if no front
+    /// matter was found.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::YAML;
+    /// let matter = Matter::<YAML>::new();
+    /// let result = matter.parse("---\ntags: [a, b]\n---\nHere is content");
+    /// let result = result.map_data(|data| data["tags"].len()).unwrap();
+    ///
+    /// assert_eq!(result.data, 2);
+    /// assert_eq!(result.content, "Here is content");
+    /// ```
+    pub fn map_data<U, F>(self, f: F) -> Option<ParsedEntityStruct<U>>
+    where
+        U: serde::de::DeserializeOwned,
+        F: FnOnce(Pod) -> U,
+    {
+        Some(ParsedEntityStruct {
+            data: f(self.data?),
+            content: self.content,
+            excerpt: self.excerpt,
+            excerpt_span: self.excerpt_span,
+            excerpt_info: self.excerpt_info,
+            content_truncated: self.content_truncated,
+            orig: self.orig,
+            matter: self.matter,
+            format: self.format,
+        })
+    }
 }
 
 /// `ParsedEntityStruct` stores the parsed result with the front matter deserialized into a struct `T`.
@@ -75,8 +134,59 @@ pub struct ParsedEntityStruct<T: serde::de::DeserializeOwned> {
     pub content: String,
     /// A string containing the excerpt, if found. `None` otherwise.
     pub excerpt: Option<String>,
+    /// The byte range of the excerpt within `orig`, if found. `None` otherwise.
+    pub excerpt_span: Option<Range<usize>>,
+    /// How the excerpt was delimited, if found. `None` otherwise, like
+    /// [`excerpt_span`](ParsedEntity::excerpt_span).
+    pub excerpt_info: Option<ExcerptInfo>,
+    /// Whether `content` was cut off at [`Matter::max_content_bytes`](crate::Matter::max_content_bytes).
+    pub content_truncated: bool,
     /// The original input.
     pub orig: String,
     /// The raw front matter. Empty string if no front matter is found.
     pub matter: String,
+    /// The [format](crate::engine::Engine::format) the front matter was parsed as. `None` if no
+    /// front matter is found, or if the engine does not report one.
+    pub format: Option<&'static str>,
+}
+
+impl<T: serde::de::DeserializeOwned> ParsedEntityStruct<T> {
+    /// Transforms the front matter with `f`, keeping every other field.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::YAML;
+    /// #[derive(serde::Deserialize)]
+    /// struct FrontMatter {
+    ///     field: i32,
+    /// }
+    ///
+    /// let matter = Matter::<YAML>::new();
+    /// let result = matter.parse_with_struct::<FrontMatter>("---\nfield: 2\n---\nHere is content");
+    /// let result = result.unwrap().map_data(|data| data.field * 2);
+    ///
+    /// assert_eq!(result.data, 4);
+    /// assert_eq!(result.content, "Here is content");
+    /// ```
+    pub fn map_data<U, F>(self, f: F) -> ParsedEntityStruct<U>
+    where
+        U: serde::de::DeserializeOwned,
+        F: FnOnce(T) -> U,
+    {
+        ParsedEntityStruct {
+            data: f(self.data),
+            content: self.content,
+            excerpt: self.excerpt,
+            excerpt_span: self.excerpt_span,
+            excerpt_info: self.excerpt_info,
+            content_truncated: self.content_truncated,
+            orig: self.orig,
+            matter: self.matter,
+            format: self.format,
+        }
+    }
 }
@@ -116,17 +116,20 @@ pub mod engine;
 #[doc(hidden)]
 pub mod entity;
 #[doc(inline)]
-pub use entity::{ParsedEntity, ParsedEntityStruct};
+pub use entity::{ExcerptInfo, ParsedEntity, ParsedEntityStruct};
 
 #[doc(hidden)]
 pub mod matter;
 #[doc(inline)]
-pub use matter::Matter;
+pub use matter::{parse, ExcerptLength, ExcerptMode, Matter, MatterPosition, ValidationIssue};
 
 #[doc(hidden)]
 pub mod value;
 #[doc(inline)]
-pub use value::{error::Error, pod::Pod};
+pub use value::{
+    error::Error,
+    pod::{PathSegment, Pod, PodType, ValidationError},
+};
 
 #[cfg(test)]
 mod tests;
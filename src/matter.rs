@@ -1,20 +1,196 @@
-use crate::engine::Engine;
-use crate::{ParsedEntity, ParsedEntityStruct};
+use crate::engine::{DuplicateKeyPolicy, DynEngine, Engine, MatterContext};
+use crate::{Error, ExcerptInfo, ParsedEntity, ParsedEntityStruct, Pod};
+use std::borrow::Cow;
 use std::fmt::Write;
+use std::fs;
+use std::io;
 use std::marker::PhantomData;
+use std::ops::Range;
+use std::path::Path;
 
 enum Part {
     Matter,
     MaybeExcerpt,
     Content,
+    /// Front matter exceeding [`Matter::max_matter_bytes`], which is never handed to the engine.
+    OversizedMatter,
+}
+
+/// Byte ranges of the parts found by [`Matter::parse_parts`], into the input as given.
+#[derive(Default)]
+struct Spans {
+    matter: Option<Range<usize>>,
+    content: Range<usize>,
+}
+
+/// Controls when [`Matter`] looks for an excerpt.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExcerptMode {
+    /// Never extract an excerpt.
+    Off,
+    /// Use the [`excerpt_delimiter`](Matter::excerpt_delimiter) if set, and fall back to the
+    /// front matter [`delimiter`](Matter::delimiter) otherwise. This is the default.
+    #[default]
+    DelimiterFallback,
+    /// Only extract an excerpt if an [`excerpt_delimiter`](Matter::excerpt_delimiter) is set.
+    Explicit,
+}
+
+/// Where in a document [`Matter`] looks for front matter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatterPosition {
+    /// At the very start of the document. This is the default.
+    #[default]
+    Leading,
+    /// At the very end of the document, where only blank lines may follow the closing delimiter.
+    /// The content is everything before the opening delimiter.
+    Trailing,
+}
+
+/// How much of the content [`Matter::auto_excerpt`] takes as an excerpt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExcerptLength {
+    /// The first words, separated by whitespace.
+    Words(usize),
+    /// The first chars.
+    Chars(usize),
+}
+
+impl ExcerptLength {
+    fn excerpt(self, content: &str) -> &str {
+        let end = match self {
+            ExcerptLength::Words(n) => match content.split_whitespace().take(n).last() {
+                Some(word) => word.as_ptr() as usize - content.as_ptr() as usize + word.len(),
+                None => 0,
+            },
+            ExcerptLength::Chars(n) => content
+                .char_indices()
+                .nth(n)
+                .map_or(content.len(), |(i, _)| i),
+        };
+        content[..end].trim_end()
+    }
+}
+
+/// A problem found by [`Matter::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// The opening front matter delimiter was found, but the closing one was not.
+    UnterminatedFrontMatter,
+    /// The front matter is enclosed by delimiters, but holds nothing.
+    EmptyFrontMatter,
+    /// The engine could not make sense of the front matter, failing with the contained error, e.g.
+    /// an [`Error::SyntaxError`] telling where.
+    InvalidFrontMatter(Error),
+    /// The delimiter line with the contained number (starting at 1) ends in whitespace, which is
+    /// stripped when parsing.
+    TrailingWhitespace(usize),
+    /// The input is not valid UTF-8 past the contained byte offset.
+    InvalidUtf8(usize),
+    /// The front matter is larger than [`Matter::max_matter_bytes`].
+    OversizedFrontMatter,
+}
+
+/// Adapts the engine type of a `Matter` into a [`DynEngine`] without requiring a value of it.
+struct StaticEngine<T: Engine>(PhantomData<T>);
+
+impl<T: Engine> Engine for StaticEngine<T> {
+    fn parse(content: &str) -> Pod {
+        T::parse(content)
+    }
+
+    fn try_parse(content: &str) -> Result<Pod, Error> {
+        T::try_parse(content)
+    }
+
+    fn parse_with_context(content: &str, ctx: &MatterContext) -> Pod {
+        T::parse_with_context(content, ctx)
+    }
+
+    fn try_parse_with_context(content: &str, ctx: &MatterContext) -> Result<Pod, Error> {
+        T::try_parse_with_context(content, ctx)
+    }
+
+    fn format() -> Option<&'static str> {
+        T::format()
+    }
 }
 
 /// Coupled with an [`Engine`](crate::engine::Engine) of choice, `Matter` stores delimiter(s) and
 /// handles parsing.
 pub struct Matter<T: Engine> {
+    /// The line opening, and by default closing, the front matter. Input is never considered to
+    /// hold front matter while it is empty.
     pub delimiter: String,
+    /// The line closing the front matter, if not the [`delimiter`](Matter::delimiter). An
+    /// empty one is ignored, as any blank line would close the front matter otherwise.
     pub close_delimiter: Option<String>,
+    /// Further lines opening front matter, tried in order when the input does not open with the
+    /// [`delimiter`](Matter::delimiter), e.g. `+++` alongside `---`. Such front matter is closed
+    /// by the same line it was opened with. Only honored for [`MatterPosition::Leading`].
+    pub alternate_delimiters: Vec<String>,
+    /// Where to look for front matter. Defaults to [`MatterPosition::Leading`].
+    pub position: MatterPosition,
+    /// The line ending the excerpt, which is ignored within fenced code blocks.
     pub excerpt_delimiter: Option<String>,
+    /// When to look for an excerpt. Defaults to [`ExcerptMode::DelimiterFallback`].
+    pub excerpt_mode: ExcerptMode,
+    /// Takes the start of the content as the excerpt, when no excerpt delimiter is found. Has no
+    /// effect with [`ExcerptMode::Off`]. The [`excerpt_span`](crate::ParsedEntity::excerpt_span)
+    /// is not set for such excerpts.
+    pub auto_excerpt: Option<ExcerptLength>,
+    /// Marks the end of the excerpt when set. The excerpt is then the text between the excerpt
+    /// delimiter and this marker, rather than everything before the excerpt delimiter.
+    pub excerpt_close_delimiter: Option<String>,
+    /// Whether the excerpt delimiter ends the excerpt wherever it occurs within a line, rather than
+    /// only at the end of one. The text before it on that line still belongs to the excerpt.
+    /// Defaults to `false`.
+    pub excerpt_delimiter_inline: bool,
+    /// Whether the excerpt delimiter is left out of [`content`](crate::ParsedEntity::content),
+    /// along with its line when nothing else is on it. Has no effect with an
+    /// [`excerpt_close_delimiter`](Matter::excerpt_close_delimiter). Defaults to `false`.
+    pub strip_excerpt_delimiter: bool,
+    /// Caps the size of [`ParsedEntity::content`](crate::ParsedEntity::content) in bytes. Content
+    /// beyond the limit is cut off at a char boundary.
+    pub max_content_bytes: Option<usize>,
+    /// Caps the size of the front matter in bytes. Larger front matter is never handed to the
+    /// engine: [`parse`](Matter::parse) gets no data, but still the content after it, and
+    /// [`try_parse`](Matter::try_parse) fails with
+    /// [`Error::MatterTooLarge`](crate::Error::MatterTooLarge).
+    pub max_matter_bytes: Option<usize>,
+    /// A line separating independent documents within a single input, used by
+    /// [`parse_all`](Matter::parse_all).
+    pub document_separator: Option<String>,
+    /// Whether leading whitespace is tolerated on the front matter fence lines, e.g. ` ---`.
+    /// Defaults to `false`.
+    pub trim_delimiter_start: bool,
+    /// Whether [`ParsedEntity::orig`](crate::ParsedEntity::orig) holds a copy of the input. Turn
+    /// this off to save the memory when the input is not needed afterwards, leaving `orig` empty.
+    /// Defaults to `true`.
+    pub keep_orig: bool,
+    /// Whether blank lines at the start of the content, e.g. right after the front matter, are
+    /// stripped. Defaults to `true`.
+    pub trim_content: bool,
+    /// Whether surrounding whitespace is trimmed off the front matter before it is handed to the
+    /// engine. Defaults to `true`.
+    pub trim_matter: bool,
+    /// What the engine does with a key occurring twice in the same mapping of the front matter.
+    /// Only honored by the YAML engines, as TOML always rejects duplicate keys. Defaults to
+    /// [`DuplicateKeyPolicy::KeepLast`].
+    pub duplicate_keys: DuplicateKeyPolicy,
+    /// A line which may additionally enclose the front matter within the delimiters, e.g. `---`
+    /// in front matter wrapped in an HTML comment. Such lines are stripped off before the front
+    /// matter is handed to the engine, and never close it.
+    pub inner_delimiter: Option<String>,
+    /// Whether a content line made of a backslash followed by a delimiter, e.g. `\---`, stands for
+    /// the delimiter itself. Such a line ends up in the content without the backslash, and is
+    /// never taken as a delimiter. Defaults to `false`.
+    pub escape_delimiters: bool,
+    /// A key of the front matter to take the excerpt from, e.g. `excerpt` or `description`. When
+    /// it holds a string, the excerpt is set from it in preference to any excerpt delimiter, and
+    /// the [`excerpt_span`](crate::ParsedEntity::excerpt_span) is not set. Has no effect with
+    /// [`ExcerptMode::Off`].
+    pub excerpt_from_key: Option<String>,
     engine: PhantomData<T>,
 }
 
@@ -24,19 +200,160 @@ impl<T: Engine> Default for Matter<T> {
     }
 }
 
+impl<T: Engine> Clone for Matter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            delimiter: self.delimiter.clone(),
+            close_delimiter: self.close_delimiter.clone(),
+            alternate_delimiters: self.alternate_delimiters.clone(),
+            position: self.position,
+            excerpt_delimiter: self.excerpt_delimiter.clone(),
+            excerpt_mode: self.excerpt_mode,
+            auto_excerpt: self.auto_excerpt,
+            excerpt_close_delimiter: self.excerpt_close_delimiter.clone(),
+            excerpt_delimiter_inline: self.excerpt_delimiter_inline,
+            strip_excerpt_delimiter: self.strip_excerpt_delimiter,
+            max_content_bytes: self.max_content_bytes,
+            max_matter_bytes: self.max_matter_bytes,
+            document_separator: self.document_separator.clone(),
+            trim_delimiter_start: self.trim_delimiter_start,
+            keep_orig: self.keep_orig,
+            trim_content: self.trim_content,
+            trim_matter: self.trim_matter,
+            duplicate_keys: self.duplicate_keys,
+            inner_delimiter: self.inner_delimiter.clone(),
+            escape_delimiters: self.escape_delimiters,
+            excerpt_from_key: self.excerpt_from_key.clone(),
+            engine: PhantomData,
+        }
+    }
+}
+
 impl<T: Engine> Matter<T> {
     pub fn new() -> Self {
         Self {
             delimiter: "---".to_string(),
             close_delimiter: None,
+            alternate_delimiters: Vec::new(),
+            position: MatterPosition::default(),
             excerpt_delimiter: None,
+            excerpt_mode: ExcerptMode::default(),
+            auto_excerpt: None,
+            excerpt_close_delimiter: None,
+            excerpt_delimiter_inline: false,
+            strip_excerpt_delimiter: false,
+            max_content_bytes: None,
+            max_matter_bytes: None,
+            document_separator: None,
+            trim_delimiter_start: false,
+            keep_orig: true,
+            trim_content: true,
+            trim_matter: true,
+            duplicate_keys: DuplicateKeyPolicy::default(),
+            inner_delimiter: None,
+            escape_delimiters: false,
+            excerpt_from_key: None,
             engine: PhantomData,
         }
     }
 
+    /// Creates a `Matter` for front matter wrapped in an HTML comment, which keeps it from being
+    /// rendered along with Markdown. The comment may hold the front matter as it is, or enclosed
+    /// in `---` delimiters.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Matter, Pod};
+    /// # use gray_matter::engine::YAML;
+    /// let matter = Matter::<YAML>::html_comment();
+    /// let parsed_entity = matter.parse("<!--\n---\ntitle: Home\n---\n-->\nOther stuff");
+    ///
+    /// assert_eq!(parsed_entity.data.unwrap()["title"], Pod::String("Home".to_owned()));
+    /// assert_eq!(parsed_entity.content, "Other stuff");
+    /// ```
+    pub fn html_comment() -> Self {
+        Self {
+            delimiter: "<!--".to_string(),
+            close_delimiter: Some("-->".to_string()),
+            inner_delimiter: Some("---".to_string()),
+            ..Matter::new()
+        }
+    }
+
+    /// Creates a `Matter` for plain Markdown, which only looks for an excerpt once an
+    /// [`excerpt_delimiter`](Matter::excerpt_delimiter) is set, rather than taking whatever comes
+    /// before a `---` line. Same as setting [`ExcerptMode::Explicit`].
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::YAML;
+    /// let matter = Matter::<YAML>::no_excerpt();
+    /// let parsed_entity = matter.parse("Intro\n---\nOther stuff");
+    ///
+    /// assert_eq!(parsed_entity.excerpt, None);
+    /// assert_eq!(parsed_entity.content, "Intro\n---\nOther stuff");
+    /// ```
+    pub fn no_excerpt() -> Self {
+        Self {
+            excerpt_mode: ExcerptMode::Explicit,
+            ..Matter::new()
+        }
+    }
+
+    /// Sets the front matter [`delimiter`](Matter::delimiter), which must not be empty.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::TOML;
+    /// let mut matter: Matter<TOML> = Matter::new();
+    /// matter.set_delimiter("+++").set_excerpt_delimiter("<!--more-->");
+    ///
+    /// assert_eq!(matter.delimiter, "+++");
+    /// assert_eq!(matter.excerpt_delimiter.as_deref(), Some("<!--more-->"));
+    /// ```
+    pub fn set_delimiter(&mut self, delimiter: &str) -> &mut Self {
+        debug_assert!(!delimiter.is_empty(), "the delimiter must not be empty");
+        self.delimiter = delimiter.to_string();
+        self
+    }
+
+    /// Sets the [`close_delimiter`](Matter::close_delimiter), which must not be empty.
+    pub fn set_close_delimiter(&mut self, close_delimiter: &str) -> &mut Self {
+        debug_assert!(
+            !close_delimiter.is_empty(),
+            "the close delimiter must not be empty"
+        );
+        self.close_delimiter = Some(close_delimiter.to_string());
+        self
+    }
+
+    /// Sets the [`excerpt_delimiter`](Matter::excerpt_delimiter), which must not be empty.
+    pub fn set_excerpt_delimiter(&mut self, excerpt_delimiter: &str) -> &mut Self {
+        debug_assert!(
+            !excerpt_delimiter.is_empty(),
+            "the excerpt delimiter must not be empty"
+        );
+        self.excerpt_delimiter = Some(excerpt_delimiter.to_string());
+        self
+    }
+
     /// Runs parsing on the input. Uses the [engine](crate::engine) contained in `self` to parse any front matter
     /// detected.
     ///
+    /// The input has to be UTF-8, see [`parse_bytes`](Matter::parse_bytes) for input of unknown
+    /// encoding.
+    ///
     /// ## Examples
     ///
     /// Basic usage:
@@ -51,90 +368,681 @@ impl<T: Engine> Matter<T> {
     /// assert_eq!(parsed_entity.content, "Other stuff");
     /// ```
     pub fn parse(&self, input: &str) -> ParsedEntity {
+        self.parse_parts(input, &StaticEngine::<T>(PhantomData)).0
+    }
+
+    /// Like [`parse`](Matter::parse), but uses `engine` instead of the engine contained in `self`
+    /// to parse any front matter detected.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Matter, Pod};
+    /// # use gray_matter::engine::{TOML, YAML};
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let input = "---\ntitle = \"Home\"\n---\nOther stuff";
+    /// let parsed_entity = matter.parse_with_engine(input, &TOML);
+    ///
+    /// assert_eq!(parsed_entity.data.unwrap()["title"], Pod::String("Home".to_owned()));
+    /// ```
+    pub fn parse_with_engine(&self, input: &str, engine: &dyn DynEngine) -> ParsedEntity {
+        self.parse_parts(input, engine).0
+    }
+
+    /// Like [`parse`](Matter::parse), but returns where the front matter, the content and the
+    /// excerpt were found instead, as byte ranges into `input`. This allows editors to highlight
+    /// them, or tools to rewrite a document without touching the rest of it.
+    ///
+    /// The front matter range covers the raw lines in between the delimiters. The content range
+    /// covers the lines [`content`](crate::ParsedEntity::content) was taken from, which only
+    /// differ from it in their line endings, as those are normalized to `\n`. The excerpt range is
+    /// [`excerpt_span`](crate::ParsedEntity::excerpt_span).
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::YAML;
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let input = "---\ntitle: Home\n---\nOther stuff";
+    /// let (matter_span, content_span, excerpt_span) = matter.parse_spans(input);
+    ///
+    /// assert_eq!(&input[matter_span.unwrap()], "title: Home");
+    /// assert_eq!(&input[content_span], "Other stuff");
+    /// assert_eq!(excerpt_span, None);
+    /// ```
+    pub fn parse_spans(
+        &self,
+        input: &str,
+    ) -> (Option<Range<usize>>, Range<usize>, Option<Range<usize>>) {
+        let (parsed_entity, _, spans) = self.parse_parts(input, &StaticEngine::<T>(PhantomData));
+        (spans.matter, spans.content, parsed_entity.excerpt_span)
+    }
+
+    /// Like [`parse`](Matter::parse), but fails with
+    /// [`Error::UnterminatedFrontMatter`](crate::Error::UnterminatedFrontMatter) when the input
+    /// opens front matter that is never closed, and with
+    /// [`Error::MatterTooLarge`](crate::Error::MatterTooLarge) when the front matter exceeds
+    /// [`max_matter_bytes`](Matter::max_matter_bytes). Engines which tell why they fail to parse
+    /// the front matter do so with [`Error::InvalidFrontMatter`](crate::Error::InvalidFrontMatter).
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Error, Matter};
+    /// # use gray_matter::engine::YAML;
+    /// let matter: Matter<YAML> = Matter::new();
+    ///
+    /// assert!(matter.try_parse("---\ntitle: Home\n---\nOther stuff").is_ok());
+    /// assert_eq!(
+    ///     matter.try_parse("---\ntitle: Home\nOther stuff"),
+    ///     Err(Error::UnterminatedFrontMatter)
+    /// );
+    /// ```
+    pub fn try_parse(&self, input: &str) -> Result<ParsedEntity, Error> {
+        self.try_parse_spans(input)
+            .map(|(parsed_entity, _)| parsed_entity)
+    }
+
+    /// [`try_parse`](Matter::try_parse), along with the spans the parts were found at.
+    fn try_parse_spans(&self, input: &str) -> Result<(ParsedEntity, Spans), Error> {
+        match self.parse_parts(input, &StaticEngine::<T>(PhantomData)) {
+            (_, Part::Matter, _) => Err(Error::UnterminatedFrontMatter),
+            (_, Part::OversizedMatter, _) => {
+                Err(Error::MatterTooLarge(self.max_matter_bytes.unwrap_or(0)))
+            }
+            (parsed_entity, _, spans) => {
+                // Only front matter without data may have failed to parse
+                if parsed_entity.data.is_none() && !parsed_entity.matter.is_empty() {
+                    T::try_parse_with_context(&parsed_entity.matter, &self.context())?;
+                }
+                Ok((parsed_entity, spans))
+            }
+        }
+    }
+
+    /// Checks a document for anything questionable, without deserializing the front matter:
+    /// unterminated or empty front matter, front matter the engine fails to parse, and trailing
+    /// whitespace on the front matter delimiter lines, which parsing strips away.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Matter, ValidationIssue};
+    /// # use gray_matter::engine::YAML;
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let issues = matter.validate("---\n--- \nOther stuff");
+    ///
+    /// assert_eq!(
+    ///     issues,
+    ///     vec![ValidationIssue::EmptyFrontMatter, ValidationIssue::TrailingWhitespace(2)]
+    /// );
+    /// ```
+    pub fn validate(&self, input: &str) -> Vec<ValidationIssue> {
+        let input = &*normalize_line_endings(input);
+        let mut issues = vec![];
+        let (parsed_entity, looking_at, _) =
+            self.parse_parts(input, &StaticEngine::<T>(PhantomData));
+        if let Part::Matter = looking_at {
+            issues.push(ValidationIssue::UnterminatedFrontMatter);
+        } else if let Part::OversizedMatter = looking_at {
+            issues.push(ValidationIssue::OversizedFrontMatter);
+        } else if self.opens_matter(input) && parsed_entity.matter.trim().is_empty() {
+            issues.push(ValidationIssue::EmptyFrontMatter);
+        }
+        if !parsed_entity.matter.is_empty() {
+            // Front matter parsing to no data, e.g. only comments, is fine
+            if let Err(err) = T::try_parse_with_context(&parsed_entity.matter, &self.context()) {
+                issues.push(ValidationIssue::InvalidFrontMatter(err));
+            }
+        }
+        if self.opens_matter(input) {
+            let close_delimiter = self.closing_delimiter();
+            for (i, line) in input.lines().enumerate() {
+                let trimmed = line.trim_end();
+                let is_fence = i == 0
+                    || self.is_fence(trimmed, &self.delimiter)
+                    || self.is_fence(trimmed, close_delimiter);
+                if is_fence && line.len() != trimmed.len() {
+                    issues.push(ValidationIssue::TrailingWhitespace(i + 1));
+                }
+                if i > 0 && is_fence {
+                    break;
+                }
+            }
+        }
+        issues
+    }
+
+    /// Like [`validate`](Matter::validate), but takes raw bytes like
+    /// [`parse_bytes`](Matter::parse_bytes) does. Invalid UTF-8 is reported as an issue, and the
+    /// valid part of the input is validated nonetheless.
+    pub fn validate_bytes(&self, bytes: &[u8]) -> Vec<ValidationIssue> {
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        match std::str::from_utf8(bytes) {
+            Ok(input) => self.validate(input),
+            Err(err) => {
+                let valid = std::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap();
+                let mut issues = self.validate(valid);
+                issues.push(ValidationIssue::InvalidUtf8(err.valid_up_to()));
+                issues
+            }
+        }
+    }
+
+    /// Whether the first line of `input` opens front matter.
+    fn opens_matter(&self, input: &str) -> bool {
+        match input.split_once('\n') {
+            Some(_) if self.delimiter.is_empty() || self.position != MatterPosition::Leading => {
+                false
+            }
+            Some((first_line, _)) => self.is_fence(first_line.trim_end(), &self.delimiter),
+            None => false,
+        }
+    }
+
+    /// Returns the first of the [`alternate_delimiters`](Matter::alternate_delimiters) which opens
+    /// front matter in `input`, unless the [`delimiter`](Matter::delimiter) itself does.
+    fn alternate_delimiter(&self, input: &str) -> Option<&str> {
+        if self.alternate_delimiters.is_empty() || self.opens_matter(input) {
+            return None;
+        }
+        let (first_line, _) = input.split_once('\n')?;
+        let first_line = first_line.trim_end();
+        self.alternate_delimiters
+            .iter()
+            .map(String::as_str)
+            .find(|delimiter| !delimiter.is_empty() && self.is_fence(first_line, delimiter))
+            .filter(|_| self.position == MatterPosition::Leading)
+    }
+
+    /// The [`close_delimiter`](Matter::close_delimiter), or the delimiter when it is unset or
+    /// empty.
+    fn closing_delimiter(&self) -> &str {
+        self.close_delimiter
+            .as_deref()
+            .filter(|delimiter| !delimiter.is_empty())
+            .unwrap_or(&self.delimiter)
+    }
+
+    fn is_fence(&self, line: &str, delimiter: &str) -> bool {
+        if self.trim_delimiter_start {
+            line.trim_start() == delimiter
+        } else {
+            line == delimiter
+        }
+    }
+
+    /// Finds the front matter at the end of `input`. Returns the byte offset of its opening
+    /// delimiter line and the byte range of the front matter.
+    fn trailing_matter(&self, input: &str, close_delimiter: &str) -> Option<(usize, Range<usize>)> {
+        if self.delimiter.is_empty() {
+            return None;
+        }
+        let lines = input.lines().collect::<Vec<_>>();
+        let close = lines.iter().rposition(|line| !line.trim().is_empty())?;
+        let closing = lines[close].trim_end();
+        if !self.is_fence(closing, &self.delimiter) && !self.is_fence(closing, close_delimiter) {
+            return None;
+        }
+        let open = lines[..close]
+            .iter()
+            .rposition(|line| self.is_fence(line.trim_end(), &self.delimiter))?;
+        let offset = lines[open].as_ptr() as usize - input.as_ptr() as usize;
+        let start = lines[open + 1].as_ptr() as usize - input.as_ptr() as usize;
+        let end = lines[close].as_ptr() as usize - input.as_ptr() as usize;
+        let span = start..start + strip_line_break(&input[start..end]).len();
+        Some((offset, span))
+    }
+
+    /// The context the engine parses the front matter in.
+    fn context(&self) -> MatterContext<'_> {
+        MatterContext {
+            delimiter: &self.delimiter,
+            close_delimiter: self.closing_delimiter(),
+            duplicate_keys: self.duplicate_keys,
+        }
+    }
+
+    /// Strips the backslash off a content line escaping any of the delimiters, if
+    /// [`escape_delimiters`](Matter::escape_delimiters) is set.
+    fn unescape<'a>(
+        &self,
+        raw_line: &'a str,
+        close_delimiter: &str,
+        excerpt_delimiter: Option<&str>,
+    ) -> Option<&'a str> {
+        if !self.escape_delimiters {
+            return None;
+        }
+        let rest = raw_line.strip_prefix('\\')?;
+        let line = rest.trim_end();
+        let escapes =
+            line == self.delimiter || line == close_delimiter || excerpt_delimiter == Some(line);
+        Some(rest).filter(|_| !line.is_empty() && escapes)
+    }
+
+    /// Strips the [`inner_delimiter`](Matter::inner_delimiter) lines off the front matter, if
+    /// they enclose all of it.
+    fn strip_inner_delimiter<'a>(&self, matter: &'a str) -> &'a str {
+        let inner = match self.inner_delimiter {
+            Some(ref inner) if !inner.is_empty() => inner.as_str(),
+            _ => return matter,
+        };
+        matter
+            .trim()
+            .strip_prefix(inner)
+            .and_then(|rest| rest.strip_suffix(inner))
+            .and_then(|rest| rest.strip_suffix('\n'))
+            .filter(|rest| rest.is_empty() || rest.starts_with('\n'))
+            .map_or(matter, |rest| rest.strip_prefix('\n').unwrap_or(rest))
+    }
+
+    /// Joins the lines of `region` with `\n`, with their trailing whitespace trimmed off if
+    /// [`trim_matter`](Matter::trim_matter) is set. Borrows `region` unless that changes it.
+    fn matter_lines<'a>(&self, region: &'a str) -> Cow<'a, str> {
+        let trim = |line: &'a str| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if self.trim_matter {
+                line.trim_end()
+            } else {
+                line
+            }
+        };
+        if region
+            .split('\n')
+            .all(|line| trim(line).len() == line.len())
+        {
+            return Cow::Borrowed(region);
+        }
+        Cow::Owned(region.split('\n').map(trim).collect::<Vec<_>>().join("\n"))
+    }
+
+    /// Hands the `region` of the input between the delimiters to `engine` as front matter.
+    fn set_matter(&self, parsed_entity: &mut ParsedEntity, region: &str, engine: &dyn DynEngine) {
+        let lines = self.matter_lines(region);
+        let matter = self.strip_inner_delimiter(&lines);
+        let matter = if self.trim_matter {
+            matter.trim()
+        } else {
+            matter
+        };
+
+        if !matter.trim().is_empty() {
+            // Front matter holding nothing but comments counts as none at all
+            let data = engine.parse_with_context(matter, &self.context());
+            let is_empty = match data {
+                Pod::Null => true,
+                Pod::Hash(ref hash) => hash.is_empty(),
+                _ => false,
+            };
+            if !is_empty {
+                parsed_entity.data = Some(data);
+                parsed_entity.format = engine.format();
+            }
+            parsed_entity.matter = matter.to_string();
+        }
+    }
+
+    /// Turns the lines accumulated by [`parse_parts`](Matter::parse_parts), each preceded by a
+    /// newline, into content.
+    fn content_of<'a>(&self, acc: &'a str) -> &'a str {
+        if self.trim_content {
+            acc.trim_start_matches('\n')
+        } else {
+            acc.strip_prefix('\n').unwrap_or(acc)
+        }
+    }
+
+    /// Sets `content` as the content of `parsed_entity`, cut off at
+    /// [`max_content_bytes`](Matter::max_content_bytes).
+    fn set_content(&self, parsed_entity: &mut ParsedEntity, mut content: &str) {
+        if let Some(max) = self.max_content_bytes {
+            if content.len() > max {
+                let end = (0..=max)
+                    .rev()
+                    .find(|&i| content.is_char_boundary(i))
+                    .unwrap();
+                content = &content[..end];
+                parsed_entity.content_truncated = true;
+            }
+        }
+        parsed_entity.content = content.to_string();
+    }
+
+    /// Finds the byte range of the content within `input[start..end]`, the lines it was reflowed
+    /// from, given its length if it was truncated.
+    fn content_span(
+        &self,
+        input: &str,
+        start: usize,
+        end: usize,
+        truncated_len: Option<usize>,
+    ) -> Range<usize> {
+        let mut region = strip_line_break(&input[start..end]);
+        if self.trim_content {
+            region = region.trim_start_matches(['\r', '\n']);
+        }
+        let start = region.as_ptr() as usize - input.as_ptr() as usize;
+        let content_len = match truncated_len {
+            Some(len) => len,
+            None => return start..start + region.len(),
+        };
+
+        // Reflowing drops the carriage return of every CRLF line ending, so count the bytes of
+        // the region it kept until the content is covered
+        let bytes = region.as_bytes();
+        let (mut len, mut kept) = (0, 0);
+        while len < bytes.len() && kept < content_len {
+            if !(bytes[len] == b'\r' && bytes.get(len + 1) == Some(&b'\n')) {
+                kept += 1;
+            }
+            len += 1;
+        }
+        start..start + len
+    }
+
+    /// Does the actual parsing, returning the part of the input that was looked at last, along
+    /// with the byte ranges of the parts found.
+    fn parse_parts(&self, input: &str, engine: &dyn DynEngine) -> (ParsedEntity, Part, Spans) {
+        // Front matter opened by an alternate delimiter is parsed as if it were the only one
+        if let Some(delimiter) = self.alternate_delimiter(input) {
+            let matter = Matter {
+                delimiter: delimiter.to_string(),
+                close_delimiter: None,
+                alternate_delimiters: Vec::new(),
+                ..self.clone()
+            };
+            return matter.parse_parts(input, engine);
+        }
+
         // Initialize ParsedEntity
         let mut parsed_entity = ParsedEntity {
             data: None,
             excerpt: None,
             content: String::new(),
-            orig: input.to_owned(),
+            orig: if self.keep_orig {
+                input.to_owned()
+            } else {
+                String::new()
+            },
             matter: String::new(),
+            format: None,
+            excerpt_span: None,
+            excerpt_info: None,
+            content_truncated: false,
         };
 
         // Check if input is empty or shorter than the delimiter
         if input.is_empty() || input.len() <= self.delimiter.len() {
-            return parsed_entity;
+            return (parsed_entity, Part::Content, Spans::default());
+        }
+
+        let input = &*normalize_line_endings(input);
+        let mut spans = Spans::default();
+
+        // If excerpt delimiter is given, use it. Otherwise, use normal delimiter unless the excerpt
+        // mode asks for an explicit one
+        let excerpt_delimiter = match self.excerpt_mode {
+            ExcerptMode::Off => None,
+            ExcerptMode::DelimiterFallback => Some(
+                self.excerpt_delimiter
+                    .clone()
+                    .unwrap_or_else(|| self.delimiter.clone()),
+            ),
+            ExcerptMode::Explicit => self.excerpt_delimiter.clone(),
+        }
+        .filter(|delimiter| !delimiter.is_empty());
+
+        let close_delimiter = self.closing_delimiter();
+        // Without any front matter or excerpt to look for, the content is the input as it is, save
+        // for CRLF line endings, which become `\n`
+        let plain = self.position == MatterPosition::Leading
+            && !(self.escape_delimiters && input.contains('\\'))
+            && !excerpt_delimiter
+                .as_deref()
+                .is_some_and(|delimiter| input.contains(delimiter))
+            && self.auto_excerpt.is_none()
+            && !self.opens_matter(input);
+        if plain {
+            let mut content = strip_line_break(input);
+            if self.trim_content {
+                content = content.trim_start_matches(['\r', '\n']);
+            }
+            if content.contains('\r') {
+                self.set_content(&mut parsed_entity, &content.replace("\r\n", "\n"));
+            } else {
+                self.set_content(&mut parsed_entity, content);
+            }
+            spans.content = self.content_span(
+                input,
+                0,
+                input.len(),
+                Some(parsed_entity.content.len()).filter(|_| parsed_entity.content_truncated),
+            );
+            return (parsed_entity, Part::MaybeExcerpt, spans);
         }
 
-        // If excerpt delimiter is given, use it. Otherwise, use normal delimiter
-        let excerpt_delimiter = self
-            .excerpt_delimiter
-            .clone()
-            .unwrap_or_else(|| self.delimiter.clone());
+        // Whether the front matter is beyond the limit, and so never handed to the engine
+        let mut oversized = false;
+
+        // Front matter at the end of the input is split off, leaving the rest to be looked at
+        let mut body = input;
+        if self.position == MatterPosition::Trailing {
+            if let Some((offset, span)) = self.trailing_matter(input, close_delimiter) {
+                let region = &input[span.clone()];
+                let len = region.len() - region.matches('\r').count();
+                oversized = matches!(self.max_matter_bytes, Some(max) if len > max);
+                if !oversized {
+                    self.set_matter(&mut parsed_entity, region, engine);
+                }
+                spans.matter = Some(span);
+                body = &input[..offset];
+            }
+        }
 
-        let close_delimiter = self
-            .close_delimiter
-            .clone()
-            .unwrap_or_else(|| self.delimiter.clone());
+        // Byte offset of the first line of the front matter
+        let mut matter_start = 0;
         // If first line starts with a delimiter followed by newline, we are looking at front
         // matter. Else, we might be looking at an excerpt.
-        let (mut looking_at, lines) = match input.split_once('\n') {
-            Some((first_line, rest)) if first_line.trim_end() == self.delimiter => {
+        let (mut looking_at, lines) = match body.split_once('\n') {
+            Some((first_line, rest)) if self.opens_matter(body) => {
+                matter_start = first_line.len() + 1;
                 (Part::Matter, rest.lines())
             }
-            _ => (Part::MaybeExcerpt, input.lines()),
+            _ => (Part::MaybeExcerpt, body.lines()),
         };
 
         let mut acc = String::new();
-        for line in lines {
-            let line = line.trim_end();
+        // Length of the front matter lines so far, once trimmed, each preceded by a newline
+        let mut matter_len = 0;
+        // Byte offset of the first line following the front matter
+        let mut content_start = None;
+        // Byte offset of the first non-blank line that may belong to an excerpt
+        let mut excerpt_start = None;
+        // Marker character and length of the fenced code block the excerpt is in, if any
+        let mut code_block = None;
+        for raw_line in lines {
+            let line = raw_line.trim_end();
+            let offset = raw_line.as_ptr() as usize - input.as_ptr() as usize;
+            let unescaped = match looking_at {
+                Part::Matter => None,
+                _ => self.unescape(raw_line, close_delimiter, excerpt_delimiter.as_deref()),
+            };
             match looking_at {
                 Part::Matter => {
-                    if line == self.delimiter || line == close_delimiter {
-                        let matter = acc.trim().to_string();
-
-                        if !matter.is_empty() {
-                            parsed_entity.data = Some(T::parse(&matter));
-                            parsed_entity.matter = matter;
+                    if self.is_fence(line, &self.delimiter) || self.is_fence(line, close_delimiter)
+                    {
+                        let matter = strip_line_break(&input[matter_start..offset]);
+                        spans.matter = Some(matter_start..matter_start + matter.len());
+                        if !oversized {
+                            self.set_matter(&mut parsed_entity, matter, engine);
                         }
-
-                        acc = String::new();
                         looking_at = Part::MaybeExcerpt;
                         continue;
                     }
+
+                    // Front matter beyond the limit never reaches the engine, but the content
+                    // after it is still looked at
+                    if matches!(self.max_matter_bytes, Some(max) if matter_len + raw_line.len() > max)
+                    {
+                        oversized = true;
+                    }
+
+                    // The front matter is handed to the engine as a slice of the input once
+                    // closed, rather than accumulated
+                    matter_len += 1 + if self.trim_matter { line } else { raw_line }.len();
+                    continue;
                 }
 
                 Part::MaybeExcerpt => {
-                    if line.ends_with(&excerpt_delimiter) {
+                    content_start.get_or_insert(offset);
+                    if excerpt_start.is_none() && !line.is_empty() {
+                        excerpt_start = Some(offset);
+                    }
+
+                    // Delimiters within fenced code blocks are part of the code, as is the fence
+                    let in_code_block = match code_block {
+                        Some((marker, len)) => {
+                            if closes_code_fence(line, marker, len) {
+                                code_block = None;
+                            }
+                            true
+                        }
+                        None => {
+                            code_block = code_fence(line);
+                            code_block.is_some()
+                        }
+                    };
+
+                    let before_delimiter = match excerpt_delimiter {
+                        _ if unescaped.is_some() || in_code_block => None,
+                        Some(_) if self.excerpt_close_delimiter.is_some() => None,
+                        Some(ref delimiter) if self.excerpt_delimiter_inline => {
+                            line.find(delimiter.as_str()).map(|i| &line[..i])
+                        }
+                        Some(ref delimiter) => line.strip_suffix(delimiter.as_str()),
+                        None => None,
+                    };
+
+                    if let Some(before_delimiter) = before_delimiter {
                         parsed_entity.excerpt = Some(
-                            format!(
-                                "{}\n{}",
-                                acc.trim_start_matches('\n'),
-                                line.strip_suffix(&excerpt_delimiter).unwrap(),
-                            )
-                            .trim_end()
-                            .to_string(),
+                            format!("{}\n{}", acc.trim_start_matches('\n'), before_delimiter)
+                                .trim_start_matches('\n')
+                                .trim_end()
+                                .to_string(),
                         );
 
+                        let start = excerpt_start.unwrap_or(offset);
+                        let end = offset + before_delimiter.len();
+                        parsed_entity.excerpt_span =
+                            Some(start..start + input[start..end].trim_end().len());
+
                         looking_at = Part::Content;
+
+                        if self.strip_excerpt_delimiter {
+                            let i = before_delimiter.len();
+                            let delimiter = excerpt_delimiter.as_deref().unwrap_or_default();
+                            let rest = &raw_line[i + delimiter.len()..];
+                            if !before_delimiter.trim().is_empty() || !rest.trim().is_empty() {
+                                write!(&mut acc, "\n{before_delimiter}{rest}").unwrap();
+                            }
+                            continue;
+                        }
+                    }
+                }
+
+                Part::OversizedMatter => unreachable!("oversized front matter is reported last"),
+
+                Part::Content => {
+                    // Once the excerpt is settled, stop accumulating past the content limit
+                    if matches!(self.max_content_bytes, Some(max) if self.content_of(&acc).len() > max)
+                    {
+                        break;
                     }
                 }
+            }
+
+            // Unlike front matter, content keeps its trailing whitespace, which is significant,
+            // e.g. for hard line breaks in Markdown
+            write!(&mut acc, "\n{}", unescaped.unwrap_or(raw_line)).unwrap();
+        }
+
+        // Unterminated front matter is taken as content, trailing whitespace included
+        if let Part::Matter = looking_at {
+            let region = strip_line_break(&body[matter_start..]);
+            acc = format!("\n{}", region.replace("\r\n", "\n"));
+        }
+
+        self.set_content(&mut parsed_entity, self.content_of(&acc));
+        spans.content = self.content_span(
+            input,
+            content_start.unwrap_or(body.len()),
+            body.len(),
+            Some(parsed_entity.content.len()).filter(|_| parsed_entity.content_truncated),
+        );
 
-                Part::Content => {}
+        if let (Some(open), Some(close), Some(from)) = (
+            &excerpt_delimiter,
+            &self.excerpt_close_delimiter,
+            content_start,
+        ) {
+            if let Some((excerpt, span)) = enclosed_excerpt(body, from, open, close) {
+                parsed_entity.excerpt = Some(excerpt);
+                parsed_entity.excerpt_span = Some(span);
             }
+        }
 
-            write!(&mut acc, "\n{line}").unwrap();
+        let delimiter_fallback = self.excerpt_mode == ExcerptMode::DelimiterFallback
+            && self
+                .excerpt_delimiter
+                .as_deref()
+                .unwrap_or_default()
+                .is_empty();
+        parsed_entity.excerpt_info = parsed_entity
+            .excerpt_span
+            .as_ref()
+            .map(|_| ExcerptInfo { delimiter_fallback });
+
+        let from_key = match (&self.excerpt_from_key, &parsed_entity.data) {
+            (Some(key), Some(data)) if self.excerpt_mode != ExcerptMode::Off => {
+                data.as_hashmap_ref().and_then(|hash| hash.get(key))
+            }
+            _ => None,
+        };
+        if let Some(Pod::String(excerpt)) = from_key {
+            parsed_entity.excerpt = Some(excerpt.clone());
+            parsed_entity.excerpt_span = None;
+            parsed_entity.excerpt_info = None;
         }
 
-        parsed_entity.content = acc.trim_start_matches('\n').to_string();
+        if let Some(length) = self.auto_excerpt {
+            let content = acc.trim_start_matches('\n');
+            let enabled = self.excerpt_mode != ExcerptMode::Off;
+            if enabled && parsed_entity.excerpt.is_none() && !content.is_empty() {
+                parsed_entity.excerpt = Some(length.excerpt(content).to_string());
+            }
+        }
 
-        parsed_entity
+        if oversized {
+            looking_at = Part::OversizedMatter;
+        }
+        (parsed_entity, looking_at, spans)
     }
 
-    /// Wrapper around [`parse`](Matter::parse), that deserializes any front matter into a custom
-    /// struct. Supplied as an ease-of-use function to prevent having to deserialize manually.
-    ///
-    /// Returns `None` if no front matter is found, or if the front matter is not deserializable
-    /// into the custom struct.
+    /// Normalizes a document: the front matter is re-serialized by the engine with sorted keys,
+    /// enclosed in the configured delimiters, and separated from the content by a single blank
+    /// line. Front matter without data comes back as an empty block, so that the content is never
+    /// taken for front matter afterwards. Documents without front matter come back as their
+    /// content.
     ///
     /// ## Examples
     ///
@@ -143,9 +1051,184 @@ impl<T: Engine> Matter<T> {
     /// ```rust
     /// # use gray_matter::Matter;
     /// # use gray_matter::engine::YAML;
-    /// # use gray_matter::ParsedEntityStruct;
-    /// #[derive(serde::Deserialize)]
-    /// struct Config {
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let input = "---   \ntitle:    Home\nauthor: me\n---\n\n\nOther stuff";
+    ///
+    /// assert_eq!(
+    ///     matter.reformat(input).unwrap(),
+    ///     "---\nauthor: me\ntitle: Home\n---\n\nOther stuff\n"
+    /// );
+    /// ```
+    pub fn reformat(&self, input: &str) -> Result<String, Error> {
+        let (parsed_entity, spans) = self.try_parse_spans(input)?;
+        let mut out = String::new();
+        if let Some(data) = parsed_entity.data {
+            let close_delimiter = self.closing_delimiter();
+            let matter = T::stringify(&data)?;
+            writeln!(
+                &mut out,
+                "{}\n{}\n{}",
+                self.delimiter,
+                matter.trim_end(),
+                close_delimiter
+            )
+            .unwrap();
+        } else if spans.matter.is_some() {
+            writeln!(&mut out, "{}\n{}", self.delimiter, self.closing_delimiter()).unwrap();
+        }
+        if !out.is_empty() && !parsed_entity.content.is_empty() {
+            out.push('\n');
+        }
+        if !parsed_entity.content.is_empty() {
+            writeln!(&mut out, "{}", parsed_entity.content.trim_end()).unwrap();
+        }
+        Ok(out)
+    }
+
+    /// Like [`parse`](Matter::parse), but takes raw bytes. A leading UTF-8 byte order mark is
+    /// stripped, and the rest of the input has to be valid UTF-8.
+    ///
+    /// Fails with [`Error::InvalidUtf8`](crate::Error::InvalidUtf8) on invalid byte sequences,
+    /// rather than panicking or replacing them.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Error, Matter};
+    /// # use gray_matter::engine::YAML;
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let parsed_entity = matter.parse_bytes(b"\xEF\xBB\xBF---\ntitle: Home\n---\nOther stuff").unwrap();
+    ///
+    /// assert_eq!(parsed_entity.content, "Other stuff");
+    /// assert_eq!(matter.parse_bytes(b"caf\xE9"), Err(Error::InvalidUtf8(3)));
+    /// ```
+    pub fn parse_bytes(&self, bytes: &[u8]) -> Result<ParsedEntity, Error> {
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        let input =
+            std::str::from_utf8(bytes).map_err(|err| Error::InvalidUtf8(err.valid_up_to()))?;
+        Ok(self.parse(input))
+    }
+
+    /// Like [`parse_bytes`](Matter::parse_bytes), but decodes the input from `encoding`, e.g. a
+    /// legacy one such as Shift_JIS or Windows-1252, rather than UTF-8. A leading byte order mark
+    /// of `encoding` is stripped.
+    ///
+    /// Fails with [`Error::InvalidEncoding`](crate::Error::InvalidEncoding) on byte sequences
+    /// which are malformed in `encoding`, rather than replacing them.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Matter, Pod};
+    /// # use gray_matter::engine::YAML;
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let parsed_entity = matter
+    ///     .parse_encoded(b"---\ntitle: Caf\xE9\n---\nOther stuff", encoding_rs::WINDOWS_1252)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(parsed_entity.data.unwrap()["title"], Pod::String("Café".to_owned()));
+    /// ```
+    #[cfg(feature = "encoding_rs")]
+    pub fn parse_encoded(
+        &self,
+        bytes: &[u8],
+        encoding: &'static encoding_rs::Encoding,
+    ) -> Result<ParsedEntity, Error> {
+        use encoding_rs::DecoderResult;
+
+        let mut decoder = encoding.new_decoder_with_bom_removal();
+        let capacity = decoder
+            .max_utf8_buffer_length_without_replacement(bytes.len())
+            .ok_or(Error::InvalidEncoding(0))?;
+        let mut input = String::with_capacity(capacity);
+        match decoder.decode_to_string_without_replacement(bytes, &mut input, true) {
+            (DecoderResult::Malformed(len, after), read) => {
+                Err(Error::InvalidEncoding(read - len as usize - after as usize))
+            }
+            _ => Ok(self.parse(&input)),
+        }
+    }
+
+    /// Reads the file at `path` and [`parse`](Matter::parse)s its content.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust,no_run
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::YAML;
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let parsed_entity = matter.parse_file("content/index.md").unwrap();
+    /// ```
+    pub fn parse_file<P: AsRef<Path>>(&self, path: P) -> io::Result<ParsedEntity> {
+        let content = fs::read_to_string(path)?;
+        Ok(self.parse(&content))
+    }
+
+    /// Splits the input on every line equal to the
+    /// [`document_separator`](Matter::document_separator) and [`parse`](Matter::parse)s each
+    /// document independently. Without a separator, the whole input is parsed as one document.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::YAML;
+    /// let mut matter: Matter<YAML> = Matter::new();
+    /// matter.document_separator = Some("===DOC===".to_string());
+    /// let input = "---\ntitle: One\n---\nFirst\n===DOC===\n---\ntitle: Two\n---\nSecond";
+    /// let parsed_entities = matter.parse_all(input);
+    ///
+    /// assert_eq!(parsed_entities.len(), 2);
+    /// assert_eq!(parsed_entities[1].content, "Second");
+    /// ```
+    pub fn parse_all(&self, input: &str) -> Vec<ParsedEntity> {
+        let separator = match self.document_separator {
+            Some(ref separator) => separator,
+            None => return vec![self.parse(input)],
+        };
+
+        let mut documents = vec![];
+        let mut start = 0;
+        for line in input.lines() {
+            if line.trim_end() == separator {
+                let offset = line.as_ptr() as usize - input.as_ptr() as usize;
+                let end = offset + line.len();
+                documents.push(&input[start..offset]);
+                start = input[end..].find('\n').map_or(input.len(), |i| end + i + 1);
+            }
+        }
+        documents.push(&input[start..]);
+
+        documents
+            .into_iter()
+            .map(|document| self.parse(document))
+            .collect()
+    }
+
+    /// Wrapper around [`parse`](Matter::parse), that deserializes any front matter into a custom
+    /// struct. Supplied as an ease-of-use function to prevent having to deserialize manually.
+    ///
+    /// Returns `None` if no front matter is found, or if the front matter is not deserializable
+    /// into the custom struct.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Matter;
+    /// # use gray_matter::engine::YAML;
+    /// # use gray_matter::ParsedEntityStruct;
+    /// #[derive(serde::Deserialize)]
+    /// struct Config {
     ///     title: String,
     /// }
     ///
@@ -159,24 +1242,230 @@ impl<T: Engine> Matter<T> {
         &self,
         input: &str,
     ) -> Option<ParsedEntityStruct<D>> {
+        self.parse_into(input).ok()
+    }
+
+    /// Like [`parse_with_struct`](Matter::parse_with_struct), but returns the front matter both as
+    /// the `Pod` it was parsed into and deserialized into the custom struct. This allows typed
+    /// access to the fields of the struct, while still inspecting any other key.
+    ///
+    /// Returns `None` in the same cases [`parse_with_struct`](Matter::parse_with_struct) does.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Matter, Pod};
+    /// # use gray_matter::engine::YAML;
+    /// #[derive(serde::Deserialize)]
+    /// struct Config {
+    ///     title: String,
+    /// }
+    ///
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let input = "---\ntitle: Home\ndraft: true\n---\nOther stuff";
+    /// let (pod, config) = matter.parse_with_pod_and_struct::<Config>(input).unwrap();
+    ///
+    /// assert_eq!(config.title, "Home");
+    /// assert_eq!(pod["draft"], Pod::Boolean(true));
+    /// ```
+    pub fn parse_with_pod_and_struct<D: serde::de::DeserializeOwned>(
+        &self,
+        input: &str,
+    ) -> Option<(Pod, D)> {
+        let data = self.parse(input).data?;
+        let typed = data.deserialize().ok()?;
+        Some((data, typed))
+    }
+
+    /// Like [`parse_with_struct`](Matter::parse_with_struct), but tells the failures apart: fails
+    /// with [`Error::ValueMissingError`](crate::Error::ValueMissingError) if no front matter is
+    /// found, and with the error of deserializing it otherwise.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Error, Matter};
+    /// # use gray_matter::engine::YAML;
+    /// #[derive(serde::Deserialize)]
+    /// struct Config {
+    ///     title: String,
+    /// }
+    ///
+    /// let matter: Matter<YAML> = Matter::new();
+    /// let parsed_entity = matter.parse_into::<Config>("---\ntitle: Home\n---\nOther stuff").unwrap();
+    ///
+    /// assert_eq!(parsed_entity.data.title, "Home");
+    /// assert!(matches!(matter.parse_into::<Config>("Other stuff"), Err(Error::ValueMissingError)));
+    /// ```
+    pub fn parse_into<D: serde::de::DeserializeOwned>(
+        &self,
+        input: &str,
+    ) -> Result<ParsedEntityStruct<D>, Error> {
         let parsed_entity = self.parse(input);
-        let data: D = parsed_entity.data?.deserialize().ok()?;
+        let data: D = parsed_entity
+            .data
+            .ok_or(Error::ValueMissingError)?
+            .deserialize()?;
 
-        Some(ParsedEntityStruct {
+        Ok(ParsedEntityStruct {
             data,
             content: parsed_entity.content,
             excerpt: parsed_entity.excerpt,
+            excerpt_span: parsed_entity.excerpt_span,
+            excerpt_info: parsed_entity.excerpt_info,
+            content_truncated: parsed_entity.content_truncated,
             orig: parsed_entity.orig,
             matter: parsed_entity.matter,
+            format: parsed_entity.format,
         })
     }
 }
 
+/// Parses `input` with a default [`Matter`] using the engine `T`, and deserializes the front
+/// matter into `D`. A shorthand for [`Matter::parse_into`] in one-off scripts.
+///
+/// ## Examples
+///
+/// Basic usage:
+///
+/// ```rust
+/// # use gray_matter::engine::YAML;
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     title: String,
+/// }
+///
+/// let parsed_entity = gray_matter::parse::<YAML, Config>("---\ntitle: Home\n---\nOther stuff").unwrap();
+///
+/// assert_eq!(parsed_entity.data.title, "Home");
+/// ```
+pub fn parse<T, D>(input: &str) -> Result<ParsedEntityStruct<D>, Error>
+where
+    T: Engine,
+    D: serde::de::DeserializeOwned,
+{
+    Matter::<T>::new().parse_into(input)
+}
+
+#[cfg(feature = "toml")]
+impl Matter<crate::engine::TOML> {
+    /// Creates a `Matter` following the [Hugo](https://gohugo.io/content-management/front-matter/)
+    /// convention for TOML front matter, which is enclosed in `+++` delimiters.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Matter, Pod};
+    /// # use gray_matter::engine::TOML;
+    /// let matter = Matter::<TOML>::hugo();
+    /// let parsed_entity = matter.parse("+++\ntitle = \"Home\"\n+++\nOther stuff");
+    ///
+    /// assert_eq!(parsed_entity.data.unwrap()["title"], Pod::String("Home".to_owned()));
+    /// ```
+    pub fn hugo() -> Self {
+        Self {
+            delimiter: "+++".to_string(),
+            ..Matter::new()
+        }
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl Matter<crate::engine::YAML> {
+    /// Creates a `Matter` following the [Hugo](https://gohugo.io/content-management/front-matter/)
+    /// convention for YAML front matter, which is enclosed in `---` delimiters.
+    pub fn hugo() -> Self {
+        Matter::new()
+    }
+}
+
+/// Turns every lone `\r`, as used for line endings by classic Mac OS, into `\n`. As both take a
+/// single byte, byte offsets into the result are valid for `input` as well. Borrows `input` unless
+/// it has any lone `\r`.
+fn normalize_line_endings(input: &str) -> Cow<'_, str> {
+    let bytes = input.as_bytes();
+    let lone_cr = |(i, &byte): (usize, &u8)| byte == b'\r' && bytes.get(i + 1) != Some(&b'\n');
+    if !bytes.iter().enumerate().any(lone_cr) {
+        return Cow::Borrowed(input);
+    }
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' if chars.peek() != Some(&'\n') => out.push('\n'),
+            c => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Strips a single trailing `\n` or `\r\n` off `s`.
+fn strip_line_break(s: &str) -> &str {
+    match s.strip_suffix('\n') {
+        Some(s) => s.strip_suffix('\r').unwrap_or(s),
+        None => s,
+    }
+}
+
+/// Returns the marker character and length of the code fence `line` opens, following
+/// CommonMark: at least three backticks or tildes, indented by up to three spaces.
+fn code_fence(line: &str) -> Option<(char, usize)> {
+    let fence = line.trim_start_matches(' ');
+    if line.len() - fence.len() > 3 {
+        return None;
+    }
+    let marker = fence.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = fence.len() - fence.trim_start_matches(marker).len();
+    // The info string of a backtick fence cannot hold backticks, as the line is inline code then
+    if len < 3 || (marker == '`' && fence[len..].contains('`')) {
+        return None;
+    }
+    Some((marker, len))
+}
+
+/// Tells whether `line` closes the code fence opened by `len` times `marker`.
+fn closes_code_fence(line: &str, marker: char, len: usize) -> bool {
+    let fence = line.trim_start_matches(' ').trim_end();
+    line.len() - line.trim_start_matches(' ').len() <= 3
+        && fence.len() >= len
+        && fence.chars().all(|c| c == marker)
+}
+
+/// Finds the excerpt enclosed by `open` and `close` in `input`, starting the search at byte offset
+/// `from`. Returns the excerpt along with its byte range within `input`.
+fn enclosed_excerpt(
+    input: &str,
+    from: usize,
+    open: &str,
+    close: &str,
+) -> Option<(String, Range<usize>)> {
+    let start = from + input[from..].find(open)? + open.len();
+    let end = start + input[start..].find(close)?;
+    let region = &input[start..end];
+    let start = start + region.len() - region.trim_start().len();
+    let span = start..start + region.trim().len();
+    let excerpt = input[span.clone()]
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some((excerpt, span))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Matter;
-    use crate::engine::{TOML, YAML};
-    use crate::ParsedEntityStruct;
+    use super::{
+        normalize_line_endings, ExcerptLength, ExcerptMode, Matter, MatterPosition, ValidationIssue,
+    };
+    use crate::engine::{Engine, MatterContext, JSON, TOML, YAML};
+    use crate::{Error, ParsedEntityStruct, Pod};
+    use std::borrow::Cow;
 
     #[test]
     fn test_front_matter() {
@@ -209,6 +1498,87 @@ mod tests {
         assert!(result.data.is_none(), "should get no front matter");
     }
 
+    #[test]
+    fn test_set_delimiters() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter
+            .set_delimiter("<!--")
+            .set_close_delimiter("-->")
+            .set_excerpt_delimiter("<!-- more -->");
+        let result = matter.parse("<!--\nabc: xyz\n-->\nfoo\n<!-- more -->\nbar");
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+    }
+
+    #[test]
+    #[should_panic(expected = "the delimiter must not be empty")]
+    #[cfg(debug_assertions)]
+    fn test_set_empty_delimiter() {
+        Matter::<YAML>::new().set_delimiter("");
+    }
+
+    #[test]
+    fn test_empty_delimiter() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.delimiter = String::new();
+        let input = "\nabc: xyz\n\ncontent\n";
+        let result = matter.parse(input);
+        assert!(result.data.is_none(), "should get no front matter");
+        assert!(result.excerpt.is_none(), "should get no excerpt");
+        assert_eq!(result.content, "abc: xyz\n\ncontent");
+        assert!(matter.try_parse(input).is_ok());
+        assert!(matter.validate(input).is_empty());
+    }
+
+    #[test]
+    fn test_empty_close_delimiter() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.close_delimiter = Some(String::new());
+        let result = matter.parse("---\ntitle: x\n\nmore: y\n---\nbody");
+        assert_eq!(result.data.unwrap()["more"], Pod::String("y".into()));
+        assert_eq!(result.content, "body");
+
+        matter.position = MatterPosition::Trailing;
+        let result = matter.parse("body\n---\ntitle: x\n\nmore: y\n---\n");
+        assert_eq!(result.data.unwrap()["more"], Pod::String("y".into()));
+        assert_eq!(result.content, "body");
+    }
+
+    #[test]
+    fn test_trailing_matter() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.position = MatterPosition::Trailing;
+        let input = "Intro\n---\nBody\n\n---\ntitle: Note\ntags: [a]\n---\n\n";
+        let result = matter.parse(input);
+        assert_eq!(result.data.unwrap()["title"], Pod::String("Note".into()));
+        assert_eq!(result.matter, "title: Note\ntags: [a]");
+        assert_eq!(result.content, "Intro\n---\nBody\n");
+        assert_eq!(result.excerpt.as_deref(), Some("Intro"));
+
+        let result = matter.parse("---\ntitle: Note\n---\nBody");
+        assert!(
+            result.data.is_none(),
+            "should ignore front matter at the start"
+        );
+        assert_eq!(result.content, "---\ntitle: Note\n---\nBody");
+
+        let result = matter.parse("Body\n---\ntitle: Note\n");
+        assert!(
+            result.data.is_none(),
+            "should get no unterminated front matter"
+        );
+
+        matter.max_matter_bytes = Some(5);
+        let input = "Body\n---\ntitle: Note\n---";
+        assert_eq!(matter.try_parse(input), Err(Error::MatterTooLarge(5)));
+        let result = matter.parse(input);
+        assert!(
+            result.data.is_none(),
+            "should get no oversized front matter"
+        );
+        assert_eq!(result.content, "Body");
+    }
+
     #[test]
     fn test_front_matter_with_different_delimiters() {
         #[derive(serde::Deserialize, PartialEq, Debug)]
@@ -226,107 +1596,755 @@ mod tests {
             "{}",
             "should get front matter as {front_matter:?}"
         );
-        matter.delimiter = "<!--".to_string();
-        matter.close_delimiter = Some("-->".to_string());
-        let result = matter.parse("---\nabc: xyz\n---");
+        matter.delimiter = "<!--".to_string();
+        matter.close_delimiter = Some("-->".to_string());
+        let result = matter.parse("---\nabc: xyz\n---");
+        assert!(result.data.is_none(), "should get no front matter");
+        let result: ParsedEntityStruct<FrontMatter> =
+            matter.parse_with_struct("<!--\nabc: xyz\n-->").unwrap();
+        assert_eq!(
+            result.data, front_matter,
+            "{}",
+            "should get front matter by custom delimiter"
+        );
+        let result = matter.parse("\nabc: xyz\n~~~");
+        assert!(result.data.is_none(), "should get no front matter");
+    }
+
+    #[test]
+    pub fn test_empty_matter() {
+        let matter: Matter<YAML> = Matter::new();
+        let table = vec![
+            "---\n---\nThis is content",
+            "---\n\n---\nThis is content",
+            "---\n\n\n\n\n\n---\nThis is content",
+        ];
+        for input in table.into_iter() {
+            let result = matter.parse(input);
+            assert!(result.data.is_none(), "should get no front matter");
+            assert_eq!(result.content, "This is content");
+        }
+    }
+
+    #[test]
+    fn test_comments_only_matter() {
+        fn check<T: Engine>(comment: &str) {
+            let matter: Matter<T> = Matter::new();
+            let result = matter.parse(&format!("---\n{comment}\n---\nThis is content"));
+            assert!(result.data.is_none(), "should get no front matter");
+            assert_eq!(result.format, None);
+            assert_eq!(result.matter, comment);
+            assert_eq!(result.content, "This is content");
+        }
+        check::<YAML>("# this is a comment");
+        check::<TOML>("# this is a comment");
+        check::<crate::engine::JSON>("{}");
+        check::<YAML>("{}");
+    }
+
+    #[test]
+    pub fn test_matter_excerpt() {
+        #[derive(serde::Deserialize, PartialEq)]
+        struct FrontMatter {
+            abc: String,
+        }
+        let mut matter: Matter<YAML> = Matter::new();
+        let result: ParsedEntityStruct<FrontMatter> = matter
+            .parse_with_struct("---\nabc: xyz\n---\nfoo\nbar\nbaz\n---\ncontent")
+            .unwrap();
+        assert_eq!(
+            result.data.abc,
+            "xyz".to_string(),
+            "should get front matter xyz as value of abc"
+        );
+        assert_eq!(
+            result.content,
+            "foo\nbar\nbaz\n---\ncontent".to_string(),
+            "should get content as \"foo\nbar\nbaz\n---\ncontent\"",
+        );
+        assert_eq!(
+            result.excerpt.unwrap(),
+            "foo\nbar\nbaz",
+            "should get an excerpt after front matter"
+        );
+        matter.excerpt_delimiter = Some("<!-- endexcerpt -->".to_string());
+        let result: ParsedEntityStruct<FrontMatter> = matter
+            .parse_with_struct("---\nabc: xyz\n---\nfoo\nbar\nbaz\n<!-- endexcerpt -->\ncontent")
+            .unwrap();
+        assert!(
+            result.data.abc == *"xyz",
+            "should get front matter xyz as value of abc"
+        );
+        assert!(
+            result.content == *"foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent",
+            "should use a custom separator"
+        );
+        assert_eq!(
+            result.excerpt.unwrap(),
+            "foo\nbar\nbaz",
+            "should get excerpt as \"foo\nbar\nbaz\""
+        );
+
+        // Check that the endexcerpt delimiter can be on the same line
+        let result: ParsedEntityStruct<FrontMatter> = matter
+            .parse_with_struct("---\nabc: xyz\n---\nfoo\nbar\nbaz<!-- endexcerpt -->\ncontent")
+            .unwrap();
+        assert!(
+            result.data.abc == *"xyz",
+            "should get front matter xyz as value of abc"
+        );
+        assert!(
+            result.content == *"foo\nbar\nbaz<!-- endexcerpt -->\ncontent",
+            "should use a custom separator"
+        );
+        assert_eq!(
+            result.excerpt.unwrap(),
+            "foo\nbar\nbaz",
+            "should get excerpt as \"foo\nbar\nbaz\""
+        );
+        let result = matter.parse("foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent");
+        assert!(result.data.is_none(), "should get no front matter");
+        assert!(
+            result.content == *"foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent",
+            "should get content as \"foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent\"",
+        );
+        assert_eq!(
+            result.excerpt.unwrap(),
+            "foo\nbar\nbaz",
+            "should use a custom separator when no front-matter exists"
+        );
+    }
+
+    #[test]
+    fn test_excerpt_shares_matter_delimiter() {
+        let matter: Matter<YAML> = Matter::new();
+        for input in [
+            "---\nk: v\n---\nexcerpt\n---\nbody",
+            "---\nk: v\n---\nexcerpt\n---\nbody\n",
+            "---\r\nk: v\r\n---\r\nexcerpt\r\n---\r\nbody",
+        ] {
+            let result = matter.parse(input);
+            assert_eq!(result.data.unwrap()["k"], Pod::String("v".into()));
+            assert_eq!(result.excerpt.as_deref(), Some("excerpt"));
+            assert_eq!(result.content, "excerpt\n---\nbody");
+            assert_eq!(&input[result.excerpt_span.unwrap()], "excerpt");
+        }
+    }
+
+    #[test]
+    fn test_excerpt_span() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "---\nabc: xyz\n---\n\nfoo\nbar  \n---\ncontent";
+        let result = matter.parse(input);
+        let span = result.excerpt_span.unwrap();
+        assert_eq!(&input[span], result.excerpt.unwrap());
+
+        matter.excerpt_delimiter = Some("<!-- endexcerpt -->".to_string());
+        let input = "foo\nbar\nbaz<!-- endexcerpt -->\ncontent";
+        let result = matter.parse(input);
+        let span = result.excerpt_span.unwrap();
+        assert_eq!(span, 0..11);
+        assert_eq!(&input[span], result.excerpt.unwrap());
+
+        let input = "<!-- endexcerpt -->\ncontent";
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt_span, Some(0..0));
+        assert_eq!(result.excerpt.unwrap(), "");
+
+        let result = matter.parse("---\nabc: xyz\n---\ncontent");
+        assert!(result.excerpt_span.is_none(), "should get no excerpt span");
+    }
+
+    #[test]
+    fn test_excerpt_info() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "---\nabc: xyz\n---\nfoo\n---\ncontent";
+        let info = matter.parse(input).excerpt_info.unwrap();
+        assert!(info.delimiter_fallback);
+
+        matter.excerpt_delimiter = Some("<!-- more -->".to_string());
+        let input = "---\nabc: xyz\n---\nfoo\n<!-- more -->\ncontent";
+        let result = matter.parse(input);
+        assert_eq!(&input[result.excerpt_span.unwrap()], "foo");
+        assert!(!result.excerpt_info.unwrap().delimiter_fallback);
+
+        matter.excerpt_delimiter = None;
+        matter.auto_excerpt = Some(ExcerptLength::Chars(3));
+        let result = matter.parse("---\nabc: xyz\n---\ncontent");
+        assert_eq!(result.excerpt.as_deref(), Some("con"));
+        assert!(
+            result.excerpt_info.is_none(),
+            "should get no info for auto excerpts"
+        );
+    }
+
+    #[test]
+    fn test_parse_spans() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "---\nabc: xyz\nfoo: bar\n---\n\nfoo\n---\ncontent\n";
+        let (matter_span, content_span, excerpt_span) = matter.parse_spans(input);
+        assert_eq!(&input[matter_span.unwrap()], "abc: xyz\nfoo: bar");
+        assert_eq!(&input[content_span], "foo\n---\ncontent");
+        assert_eq!(&input[excerpt_span.unwrap()], "foo");
+
+        let input = "---\r\nabc: xyz\r\n---\r\ncontent\r\nmore\r\n";
+        let (matter_span, content_span, _) = matter.parse_spans(input);
+        assert_eq!(&input[matter_span.unwrap()], "abc: xyz");
+        assert_eq!(&input[content_span], "content\r\nmore");
+
+        let input = "---\n---\ncontent";
+        let (matter_span, content_span, _) = matter.parse_spans(input);
+        assert_eq!(matter_span, Some(4..4));
+        assert_eq!(&input[content_span], "content");
+
+        let input = "\n\nno front matter\n";
+        let (matter_span, content_span, _) = matter.parse_spans(input);
+        assert!(matter_span.is_none(), "should get no front matter span");
+        assert_eq!(&input[content_span], "no front matter");
+
+        matter.position = MatterPosition::Trailing;
+        let input = "content\n\n---\nabc: xyz\n---\n";
+        let (matter_span, content_span, _) = matter.parse_spans(input);
+        assert_eq!(&input[matter_span.unwrap()], "abc: xyz");
+        assert_eq!(&input[content_span], "content\n");
+
+        matter.position = MatterPosition::Leading;
+        matter.max_content_bytes = Some(9);
+        let input = "---\nabc: xyz\n---\ncontent\r\nmore\r\n";
+        let (_, content_span, _) = matter.parse_spans(input);
+        assert_eq!(&input[content_span], "content\r\nm");
+    }
+
+    #[test]
+    fn test_excerpt_delimiter_inline() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.excerpt_delimiter = Some("<!--more-->".to_string());
+        let input = "---\nabc: xyz\n---\nfoo\nbar <!--more-->text\ncontent";
+        let result = matter.parse(input);
+        assert!(
+            result.excerpt.is_none(),
+            "should only match at the end of a line"
+        );
+
+        matter.excerpt_delimiter_inline = true;
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("foo\nbar"));
+        assert_eq!(&input[result.excerpt_span.unwrap()], "foo\nbar");
+        assert_eq!(result.content, "foo\nbar <!--more-->text\ncontent");
+
+        let result = matter.parse("foo<!--more-->bar<!--more-->baz");
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+        let result = matter.parse("foo\nbar<!--more-->");
+        assert_eq!(result.excerpt.as_deref(), Some("foo\nbar"));
+    }
+
+    #[test]
+    fn test_strip_excerpt_delimiter() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.excerpt_delimiter = Some("<!-- endexcerpt -->".to_string());
+        let input = "---\nabc: xyz\n---\nfoo\nbar\n<!-- endexcerpt -->\ncontent";
+        let result = matter.parse(input);
+        assert_eq!(result.content, "foo\nbar\n<!-- endexcerpt -->\ncontent");
+
+        matter.strip_excerpt_delimiter = true;
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("foo\nbar"));
+        assert_eq!(result.content, "foo\nbar\ncontent");
+
+        let result = matter.parse("foo\nbar<!-- endexcerpt -->\ncontent");
+        assert_eq!(result.excerpt.as_deref(), Some("foo\nbar"));
+        assert_eq!(result.content, "foo\nbar\ncontent");
+
+        matter.excerpt_delimiter_inline = true;
+        let result = matter.parse("foo <!-- endexcerpt --> bar\ncontent");
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+        assert_eq!(result.content, "foo  bar\ncontent");
+    }
+
+    #[test]
+    fn test_enclosed_excerpt() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.excerpt_delimiter = Some("<!--excerpt-->".to_string());
+        matter.excerpt_close_delimiter = Some("<!--/excerpt-->".to_string());
+        let input = "---\nabc: xyz\n---\nintro\n<!--excerpt-->\nfoo\nbar\n<!--/excerpt-->\ncontent";
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("foo\nbar"));
+        assert_eq!(&input[result.excerpt_span.unwrap()], "foo\nbar");
+        assert_eq!(
+            result.content, "intro\n<!--excerpt-->\nfoo\nbar\n<!--/excerpt-->\ncontent",
+            "should keep the excerpt markers in content"
+        );
+
+        let result = matter.parse("intro <!--excerpt-->foo<!--/excerpt--> content");
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+
+        let result = matter.parse("intro\n<!--excerpt-->\nfoo\nbar");
+        assert!(
+            result.excerpt.is_none(),
+            "should get no excerpt without a close marker"
+        );
+
+        let result = matter.parse("---\nabc: xyz\n---\nfoo\n---\ncontent");
+        assert!(
+            result.excerpt.is_none(),
+            "should not fall back to the excerpt delimiter alone"
+        );
+    }
+
+    #[test]
+    fn test_parse_all() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "---\nabc: one\n---\nfirst\n===DOC===\n---\nabc: two\n---\nsecond\n";
+        let result = matter.parse_all(input);
+        assert_eq!(
+            result.len(),
+            1,
+            "should get one document without a separator"
+        );
+
+        matter.document_separator = Some("===DOC===".to_string());
+        let result = matter.parse_all(input);
+        assert_eq!(result.len(), 2, "should split on the document separator");
+        assert_eq!(
+            result[0].data.as_ref().unwrap()["abc"],
+            Pod::String("one".into())
+        );
+        assert_eq!(result[0].content, "first");
+        assert_eq!(
+            result[1].data.as_ref().unwrap()["abc"],
+            Pod::String("two".into())
+        );
+        assert_eq!(result[1].content, "second");
+    }
+
+    #[test]
+    fn test_parse_into() {
+        #[derive(serde::Deserialize, PartialEq, Debug)]
+        struct FrontMatter {
+            abc: String,
+        }
+        let matter: Matter<YAML> = Matter::new();
+        let result = matter.parse_into::<FrontMatter>("---\nabc: xyz\n---\ncontent");
+        assert_eq!(result.unwrap().data.abc, "xyz");
+        let result = matter.parse_into::<FrontMatter>("content");
+        assert!(matches!(result, Err(Error::ValueMissingError)));
+        let result = matter.parse_into::<FrontMatter>("---\nxyz: abc\n---\ncontent");
+        assert!(
+            matches!(result, Err(Error::DeserializeError(ref msg)) if msg.contains("abc")),
+            "should get the deserialize error"
+        );
+    }
+
+    #[test]
+    fn test_parse_with_pod_and_struct() {
+        #[derive(serde::Deserialize, PartialEq, Debug)]
+        struct FrontMatter {
+            abc: String,
+            tags: Vec<String>,
+        }
+        let matter: Matter<YAML> = Matter::new();
+        let input = "---\nabc: xyz\ntags: [a, b]\nextra: 1\n---\ncontent";
+        let (pod, data) = matter
+            .parse_with_pod_and_struct::<FrontMatter>(input)
+            .unwrap();
+        assert_eq!(pod["abc"], Pod::String(data.abc));
+        assert_eq!(pod["tags"].to_vec_of::<String>().unwrap(), data.tags);
+        assert_eq!(pod["extra"], Pod::Integer(1));
+        assert!(matter
+            .parse_with_pod_and_struct::<FrontMatter>("content")
+            .is_none());
+        assert!(matter
+            .parse_with_pod_and_struct::<FrontMatter>("---\nabc: xyz\n---\ncontent")
+            .is_none());
+    }
+
+    #[test]
+    fn test_unterminated_front_matter() {
+        let matter: Matter<YAML> = Matter::new();
+        let result = matter.try_parse("---\ntitle: x");
+        assert_eq!(result, Err(Error::UnterminatedFrontMatter));
+        let result = matter.parse("---\ntitle: x");
+        assert!(result.data.is_none(), "should get no front matter");
+        let result = matter.parse("---\ntitle: x\nline one  \r\nline two\n");
+        assert_eq!(result.content, "title: x\nline one  \nline two");
+        assert!(matter.try_parse("---\ntitle: x\n---").is_ok());
+        assert!(matter.try_parse("title: x\n---\ncontent").is_ok());
+        assert!(matter.try_parse("").is_ok());
+    }
+
+    #[test]
+    fn test_max_content_bytes() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.max_content_bytes = Some(9);
+        let result = matter.parse("---\nabc: xyz\n---\nfoo\n---\nbar\nbaz\nqux");
+        assert_eq!(result.content, "foo\n---\nb");
+        assert!(result.content_truncated, "should flag truncated content");
+        assert_eq!(result.excerpt.unwrap(), "foo");
+
+        // "é" takes two bytes, so the limit falls in the middle of it
+        matter.max_content_bytes = Some(4);
+        let result = matter.parse("---\nabc: xyz\n---\nabcé");
+        assert_eq!(result.content, "abc", "should truncate at a char boundary");
+        assert!(result.content_truncated);
+
+        matter.max_content_bytes = Some(5);
+        let result = matter.parse("---\nabc: xyz\n---\nabcé");
+        assert_eq!(result.content, "abcé");
+        assert!(
+            !result.content_truncated,
+            "should not flag content within the limit"
+        );
+    }
+
+    #[test]
+    fn test_max_matter_bytes() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.max_matter_bytes = Some(17);
+        let input = "---\nabc: xyz\nfoo: bar\n---\ncontent";
+        let result = matter.parse(input);
+        assert!(
+            result.data.is_some(),
+            "should parse front matter within the limit"
+        );
+
+        let input = "---\nabc: xyz\nfoo: bar\nbaz: qux\n---\ncontent";
+        let result = matter.parse(input);
         assert!(result.data.is_none(), "should get no front matter");
-        let result: ParsedEntityStruct<FrontMatter> =
-            matter.parse_with_struct("<!--\nabc: xyz\n-->").unwrap();
+        assert_eq!(result.matter, "");
+        assert_eq!(result.content, "content", "should keep the content");
+        assert_eq!(matter.try_parse(input), Err(Error::MatterTooLarge(17)));
         assert_eq!(
-            result.data, front_matter,
-            "{}",
-            "should get front matter by custom delimiter"
+            matter.validate(input),
+            vec![ValidationIssue::OversizedFrontMatter]
         );
-        let result = matter.parse("\nabc: xyz\n~~~");
-        assert!(result.data.is_none(), "should get no front matter");
     }
 
     #[test]
-    pub fn test_empty_matter() {
+    fn test_parse_with_engine() {
         let matter: Matter<YAML> = Matter::new();
-        let table = vec![
-            "---\n---\nThis is content",
-            "---\n\n---\nThis is content",
-            "---\n\n\n\n\n\n---\nThis is content",
-        ];
-        for input in table.into_iter() {
-            let result = matter.parse(input);
-            assert!(result.data.is_none(), "should get no front matter");
-            assert_eq!(result.content, "This is content");
-        }
+        let input = "---\nabc = \"xyz\"\n---\ncontent";
+        let result = matter.parse(input);
+        assert_ne!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        let result = matter.parse_with_engine(input, &TOML);
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.format, Some("toml"));
+        assert_eq!(result.content, "content");
     }
 
     #[test]
-    pub fn test_matter_excerpt() {
-        #[derive(serde::Deserialize, PartialEq)]
-        struct FrontMatter {
-            abc: String,
+    fn test_excerpt_mode() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "Intro\n---\nBody";
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("Intro"));
+
+        matter.excerpt_mode = ExcerptMode::Explicit;
+        let result = matter.parse(input);
+        assert!(
+            result.excerpt.is_none(),
+            "should get no excerpt without an explicit excerpt delimiter"
+        );
+        assert_eq!(result.content, "Intro\n---\nBody");
+        matter.excerpt_delimiter = Some("<!-- more -->".to_string());
+        let result = matter.parse("Intro\n<!-- more -->\nBody");
+        assert_eq!(result.excerpt.as_deref(), Some("Intro"));
+
+        matter.excerpt_mode = ExcerptMode::Off;
+        let result = matter.parse("Intro\n<!-- more -->\nBody");
+        assert!(result.excerpt.is_none(), "should never get an excerpt");
+    }
+
+    #[test]
+    fn test_plain_content() {
+        let mut plain: Matter<YAML> = Matter::new();
+        plain.excerpt_mode = ExcerptMode::Off;
+        // Every input holds this delimiter, which no line can, so it is reflowed nonetheless
+        let mut reflowed: Matter<YAML> = Matter::new();
+        reflowed.excerpt_delimiter = Some("\n".to_string());
+        for (trim_content, max_content_bytes) in [(true, None), (false, None), (true, Some(5))] {
+            plain.trim_content = trim_content;
+            reflowed.trim_content = trim_content;
+            plain.max_content_bytes = max_content_bytes;
+            reflowed.max_content_bytes = max_content_bytes;
+            for input in [
+                "foo\nbar",
+                "\n\nfoo  \n\nbar\n\n",
+                "foo\r\nbar\r\n",
+                "\r\n\r\nfoo  \r\n\r\nbar\r\n\r\n",
+                "foo\rbar",
+                "---whatever\n---\n",
+                "é\n",
+            ] {
+                assert_eq!(plain.parse(input), reflowed.parse(input), "{:?}", input);
+                assert_eq!(
+                    plain.parse_spans(input),
+                    reflowed.parse_spans(input),
+                    "{:?}",
+                    input
+                );
+            }
         }
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        assert!(matches!(
+            normalize_line_endings("foo\r\nbar\r\n"),
+            Cow::Borrowed("foo\r\nbar\r\n")
+        ));
+        assert_eq!(
+            normalize_line_endings("foo\rbar\r\nbaz\r"),
+            "foo\nbar\r\nbaz\n"
+        );
+    }
+
+    #[test]
+    fn test_keep_orig() {
         let mut matter: Matter<YAML> = Matter::new();
-        let result: ParsedEntityStruct<FrontMatter> = matter
-            .parse_with_struct("---\nabc: xyz\n---\nfoo\nbar\nbaz\n---\ncontent")
-            .unwrap();
+        let input = "---\nabc: xyz\n---\ncontent";
+        assert_eq!(matter.parse(input).orig, input);
+
+        matter.keep_orig = false;
+        let result = matter.parse(input);
+        assert_eq!(result.orig, "");
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.content, "content");
+    }
+
+    #[test]
+    fn test_trim_content() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "---\nabc: xyz\n---\n\n\nfoo\n---\nbar\n\n";
+        let result = matter.parse(input);
+        assert_eq!(result.content, "foo\n---\nbar\n");
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+
+        matter.trim_content = false;
+        let result = matter.parse(input);
+        assert_eq!(result.content, "\n\nfoo\n---\nbar\n");
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+        let result = matter.parse("\nfoo");
+        assert_eq!(result.content, "\nfoo");
+    }
+
+    #[test]
+    fn test_trim_delimiter_start() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = " ---\nabc: xyz\n  ---\ncontent";
+        let result = matter.parse(input);
+        assert!(result.data.is_none(), "should be strict by default");
+
+        matter.trim_delimiter_start = true;
+        let result = matter.parse(input);
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.content, "content");
+
+        let input = "\t---\r\n    abc: xyz\r\n    ---\r\n  excerpt\r\n  ---\r\ncontent";
+        let result = matter.parse(input);
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.excerpt.as_deref(), Some("  excerpt"));
+        assert_eq!(result.content, "  excerpt\n  ---\ncontent");
+        let result = matter.parse("  ---abc\nabc: xyz\n---\ncontent");
+        assert!(
+            result.data.is_none(),
+            "should still require the delimiter to fill the line"
+        );
+    }
+
+    #[test]
+    fn test_reformat() {
+        let matter: Matter<YAML> = Matter::new();
+        let input = "---  \n\nzeta:   1\nalpha:\n    - b\n    - a\nmid: {x: 1.0}\n\n---\n\n\n# Title\n\nBody  \n\n";
+        let expected =
+            "---\nalpha:\n  - b\n  - a\nmid:\n  x: 1.0\nzeta: 1\n---\n\n# Title\n\nBody\n";
+        let result = matter.reformat(input).unwrap();
+        assert_eq!(result, expected);
         assert_eq!(
-            result.data.abc,
-            "xyz".to_string(),
-            "should get front matter xyz as value of abc"
+            matter.reformat(&result).unwrap(),
+            expected,
+            "should be stable"
         );
+
+        let matter: Matter<TOML> = Matter::new();
+        let input = "---\nb = 2\n[t]\nz = true\n\ny = \"x\"\n---\nBody";
+        let result = matter.reformat(input).unwrap();
         assert_eq!(
-            result.content,
-            "foo\nbar\nbaz\n---\ncontent".to_string(),
-            "should get content as \"foo\nbar\nbaz\n---\ncontent\"",
+            result,
+            "---\nb = 2\n\n[t]\ny = \"x\"\nz = true\n---\n\nBody\n"
         );
         assert_eq!(
-            result.excerpt.unwrap(),
-            "foo\nbar\nbaz",
-            "should get an excerpt after front matter"
+            matter.reformat(&result).unwrap(),
+            result,
+            "should be stable"
         );
-        matter.excerpt_delimiter = Some("<!-- endexcerpt -->".to_string());
-        let result: ParsedEntityStruct<FrontMatter> = matter
-            .parse_with_struct("---\nabc: xyz\n---\nfoo\nbar\nbaz\n<!-- endexcerpt -->\ncontent")
+
+        assert_eq!(matter.reformat("Body").unwrap(), "Body\n");
+        let matter: Matter<YAML> = Matter::new();
+        let result = matter
+            .reformat("---\n# just a comment\n---\nbody\n")
             .unwrap();
-        assert!(
-            result.data.abc == *"xyz",
-            "should get front matter xyz as value of abc"
-        );
-        assert!(
-            result.content == *"foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent",
-            "should use a custom separator"
+        assert_eq!(result, "---\n---\n\nbody\n");
+        assert_eq!(
+            matter.reformat(&result).unwrap(),
+            result,
+            "should be stable"
         );
+        let result = matter.reformat("---\n---\n---\nbody\n").unwrap();
+        assert_eq!(result, "---\n---\n\n---\nbody\n");
         assert_eq!(
-            result.excerpt.unwrap(),
-            "foo\nbar\nbaz",
-            "should get excerpt as \"foo\nbar\nbaz\""
+            matter.reformat(&result).unwrap(),
+            result,
+            "should be stable"
         );
-
-        // Check that the endexcerpt delimiter can be on the same line
-        let result: ParsedEntityStruct<FrontMatter> = matter
-            .parse_with_struct("---\nabc: xyz\n---\nfoo\nbar\nbaz<!-- endexcerpt -->\ncontent")
-            .unwrap();
-        assert!(
-            result.data.abc == *"xyz",
-            "should get front matter xyz as value of abc"
+        assert_eq!(
+            matter.reformat("---\n---\nbody\n").unwrap(),
+            "---\n---\n\nbody\n"
         );
-        assert!(
-            result.content == *"foo\nbar\nbaz<!-- endexcerpt -->\ncontent",
-            "should use a custom separator"
+        let matter: Matter<TOML> = Matter::new();
+        assert_eq!(
+            matter.reformat("---\nb = 2"),
+            Err(Error::UnterminatedFrontMatter)
         );
+    }
+
+    #[test]
+    fn test_parse_with_struct_error_path() {
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Analytics {
+            alexa: Vec<i64>,
+        }
+        #[derive(serde::Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct FrontMatter {
+            analytics: Analytics,
+        }
+        let matter: Matter<YAML> = Matter::new();
+        let input = "---\nanalytics:\n  alexa: [1, two]\n---\ncontent";
+        assert!(matter.parse_with_struct::<FrontMatter>(input).is_none());
+        match matter.parse_into::<FrontMatter>(input) {
+            Err(Error::DeserializeError(msg)) => {
+                assert!(
+                    msg.starts_with("at analytics.alexa.1: invalid type"),
+                    "{}",
+                    msg
+                )
+            }
+            result => panic!("should fail at the path, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let matter: Matter<YAML> = Matter::new();
+        assert!(matter.validate("---\nabc: xyz\n---\ncontent").is_empty());
+        assert!(matter.validate("content").is_empty());
         assert_eq!(
-            result.excerpt.unwrap(),
-            "foo\nbar\nbaz",
-            "should get excerpt as \"foo\nbar\nbaz\""
+            matter.validate("--- \nabc: xyz \ncontent  "),
+            vec![
+                ValidationIssue::UnterminatedFrontMatter,
+                ValidationIssue::TrailingWhitespace(1)
+            ]
         );
-        let result = matter.parse("foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent");
-        assert!(result.data.is_none(), "should get no front matter");
-        assert!(
-            result.content == *"foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent",
-            "should get content as \"foo\nbar\nbaz\n<!-- endexcerpt -->\ncontent\"",
+        assert_eq!(
+            matter.validate("---\n\n---  \ncontent"),
+            vec![
+                ValidationIssue::EmptyFrontMatter,
+                ValidationIssue::TrailingWhitespace(3)
+            ]
         );
+        match matter
+            .validate_bytes(b"---\nabc: [xyz\n---\ncaf\xE9")
+            .as_slice()
+        {
+            [ValidationIssue::InvalidFrontMatter(Error::InvalidFrontMatter(msg)), ValidationIssue::InvalidUtf8(21)] =>
+            {
+                assert!(msg.contains("expected ',' or ']'"), "{}", msg)
+            }
+            issues => panic!("should report the invalid front matter, got {:?}", issues),
+        }
+        assert!(matter
+            .validate("---\n# just a comment\n---\ncontent")
+            .is_empty());
+        assert!(matter.validate("---\nnull\n---\ncontent").is_empty());
+
+        let matter: Matter<JSON> = Matter::new();
+        let issues =
+            matter.validate("---\n{\n  \"title\": \"Home\"\n  \"draft\": true\n}\n---\ncontent");
+        match issues.as_slice() {
+            [ValidationIssue::InvalidFrontMatter(Error::SyntaxError { line, column, .. })] => {
+                assert_eq!((*line, *column), (3, 3))
+            }
+            issues => panic!("should report the syntax error, got {:?}", issues),
+        }
+    }
+
+    #[test]
+    fn test_trailing_spaces_in_content() {
+        fn check<T: Engine>(matter: &str) {
+            let matter_parser: Matter<T> = Matter::new();
+            let content = "line one  \nline two\t\n\n    code  \nlast line  ";
+            let result = matter_parser.parse(&format!("---\n{matter}\n---\n{content}"));
+            assert!(result.data.is_some(), "should get front matter");
+            assert_eq!(result.content, content, "should preserve trailing spaces");
+            let result = matter_parser.parse(&format!("---\n{matter}\n---\n{content}\n"));
+            assert_eq!(result.content, content);
+            let crlf_content = content.replace('\n', "\r\n");
+            let result = matter_parser.parse(&format!("---\r\n{matter}\r\n---\r\n{crlf_content}"));
+            assert_eq!(result.content, content);
+        }
+        check::<YAML>("abc: xyz");
+        check::<TOML>("abc = \"xyz\"");
+        check::<crate::engine::JSON>("{\"abc\": \"xyz\"}");
+
+        let matter: Matter<YAML> = Matter::new();
+        let result = matter.parse("excerpt  \n---\ncontent  ");
+        assert_eq!(result.content, "excerpt  \n---\ncontent  ");
+        assert_eq!(result.excerpt.unwrap(), "excerpt");
+    }
+
+    #[test]
+    fn test_lone_carriage_returns() {
+        let matter: Matter<YAML> = Matter::new();
+        let input = "---\rabc: xyz\r---\rfoo\r---\rbar\r\nbaz";
+        let result = matter.parse(input);
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+        assert_eq!(&input[result.excerpt_span.unwrap()], "foo");
+        assert_eq!(result.content, "foo\n---\nbar\nbaz");
+        assert_eq!(result.orig, input);
+        assert!(matter.validate(input).is_empty());
+    }
+
+    #[test]
+    fn test_auto_excerpt() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.excerpt_delimiter = Some("<!-- more -->".to_string());
+        let input = "---\nabc: xyz\n---\n\nThe quick  brown\nfox jumps over the lazy dog";
+        assert!(matter.parse(input).excerpt.is_none());
+
+        matter.auto_excerpt = Some(ExcerptLength::Words(4));
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("The quick  brown\nfox"));
+        assert!(result.excerpt_span.is_none());
+
+        matter.auto_excerpt = Some(ExcerptLength::Chars(10));
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("The quick"));
+
+        matter.auto_excerpt = Some(ExcerptLength::Words(100));
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.unwrap(), result.content);
+
+        let result = matter.parse("Intro\n<!-- more -->\nBody");
         assert_eq!(
-            result.excerpt.unwrap(),
-            "foo\nbar\nbaz",
-            "should use a custom separator when no front-matter exists"
+            result.excerpt.as_deref(),
+            Some("Intro"),
+            "should prefer the excerpt delimiter"
         );
+
+        matter.excerpt_mode = ExcerptMode::Off;
+        assert!(matter.parse(input).excerpt.is_none());
     }
 
     #[test]
@@ -457,6 +2475,194 @@ field2 = [3.14, 42]
         assert_eq!(result.content, "    this is code block\n\n# This is header")
     }
 
+    #[test]
+    fn test_untrimmed_matter() {
+        struct Verbatim;
+        impl Engine for Verbatim {
+            fn parse(content: &str) -> Pod {
+                Pod::String(content.to_string())
+            }
+        }
+
+        let raw = "---\n\n  indented: true  \n\n---\ncontent";
+        let mut matter: Matter<Verbatim> = Matter::new();
+        let result = matter.parse(raw);
+        assert_eq!(result.data, Some(Pod::String("indented: true".to_string())));
+
+        matter.trim_matter = false;
+        let result = matter.parse(raw);
+        assert_eq!(
+            result.data,
+            Some(Pod::String("\n  indented: true  \n".to_string())),
+            "should pass the matter verbatim to the engine"
+        );
+        assert_eq!(result.matter, "\n  indented: true  \n");
+        assert_eq!(result.content, "content");
+        assert_eq!(result.format, None);
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_parse_encoded() {
+        let matter: Matter<YAML> = Matter::new();
+        let input = b"---\ntitle: Caf\xE9\n---\nna\xEFve";
+        let result = matter
+            .parse_encoded(input, encoding_rs::WINDOWS_1252)
+            .unwrap();
+        assert_eq!(
+            result.data.unwrap()["title"],
+            Pod::String("Caf\u{e9}".into())
+        );
+        assert_eq!(result.content, "na\u{ef}ve");
+        assert_eq!(matter.parse_bytes(input), Err(Error::InvalidUtf8(14)));
+
+        let input = b"---\ntitle: \x93\xfa\x96\x7b\n---\ncontent";
+        let result = matter.parse_encoded(input, encoding_rs::SHIFT_JIS).unwrap();
+        assert_eq!(
+            result.data.unwrap()["title"],
+            Pod::String("\u{65e5}\u{672c}".into())
+        );
+
+        let result = matter.parse_encoded(b"ab\x81\x20cd", encoding_rs::SHIFT_JIS);
+        assert_eq!(result, Err(Error::InvalidEncoding(2)));
+    }
+
+    #[test]
+    fn test_escape_delimiters() {
+        let mut matter: Matter<YAML> = Matter::new();
+        let input = "---\nabc: xyz\n---\nfoo\n\\---\nbar\n---\ncontent";
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("foo\n\\"));
+        assert_eq!(result.content, "foo\n\\---\nbar\n---\ncontent");
+
+        matter.escape_delimiters = true;
+        let result = matter.parse(input);
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.excerpt.as_deref(), Some("foo\n---\nbar"));
+        assert_eq!(result.content, "foo\n---\nbar\n---\ncontent");
+
+        let result = matter.parse("\\---\nabc: xyz\n---\ncontent");
+        assert!(result.data.is_none(), "should not open front matter");
+        assert_eq!(result.excerpt.as_deref(), Some("---\nabc: xyz"));
+        assert_eq!(result.content, "---\nabc: xyz\n---\ncontent");
+
+        matter.excerpt_mode = ExcerptMode::Off;
+        let result = matter.parse("\\---\n\\--- \n\\\\---\n\\-\ncontent");
+        assert_eq!(result.content, "---\n--- \n\\\\---\n\\-\ncontent");
+    }
+
+    #[test]
+    fn test_excerpt_from_key() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.excerpt_from_key = Some("excerpt".to_string());
+        let input = "---\ntitle: Home\nexcerpt: From the key\n---\nfoo\n---\ncontent";
+        let result = matter.parse(input);
+        assert_eq!(result.excerpt.as_deref(), Some("From the key"));
+        assert!(result.excerpt_span.is_none(), "should get no excerpt span");
+        assert!(result.excerpt_info.is_none(), "should get no excerpt info");
+        assert_eq!(result.content, "foo\n---\ncontent");
+
+        let result = matter.parse("---\ntitle: Home\n---\nfoo\n---\ncontent");
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+        let result = matter.parse("---\nexcerpt: [foo]\n---\nfoo\n---\ncontent");
+        assert_eq!(result.excerpt.as_deref(), Some("foo"));
+
+        matter.excerpt_mode = ExcerptMode::Explicit;
+        let result = matter.parse("---\nexcerpt: From the key\n---\ncontent");
+        assert_eq!(result.excerpt.as_deref(), Some("From the key"));
+        matter.excerpt_mode = ExcerptMode::Off;
+        assert!(matter.parse(input).excerpt.is_none());
+    }
+
+    #[test]
+    fn test_html_comment() {
+        let matter = Matter::<YAML>::html_comment();
+        let input = "<!--\n---\ntitle: Home\ntags: [a, b]\n---\n-->\n# Heading\n\ncontent";
+        let result = matter.parse(input);
+        let data = result.data.unwrap();
+        assert_eq!(data["title"], Pod::String("Home".into()));
+        assert_eq!(data["tags"].len(), 2);
+        assert_eq!(result.matter, "title: Home\ntags: [a, b]");
+        assert_eq!(result.content, "# Heading\n\ncontent");
+
+        let result = matter.parse("<!--\ntitle: Home\n-->\ncontent");
+        assert_eq!(result.data.unwrap()["title"], Pod::String("Home".into()));
+        assert_eq!(result.content, "content");
+
+        let result = matter.parse("<!--\n---\n---\n-->\ncontent");
+        assert!(result.data.is_none(), "should get no front matter");
+        assert_eq!(result.content, "content");
+
+        let matter = Matter::<TOML>::html_comment();
+        let result = matter.parse("<!--\n---\ntitle = \"Home\"\n---\n-->\ncontent");
+        assert_eq!(result.data.unwrap()["title"], Pod::String("Home".into()));
+
+        let mut matter = Matter::<TOML>::html_comment();
+        matter.inner_delimiter = None;
+        let result = matter.parse("<!--\n---\ntitle = \"Home\"\n---\n-->\ncontent");
+        assert!(
+            result.data.is_none(),
+            "should hand the inner delimiters to the engine"
+        );
+    }
+
+    #[test]
+    fn test_hugo() {
+        let matter = Matter::<TOML>::hugo();
+        let result = matter.parse("+++\nabc = \"xyz\"\n+++\ncontent");
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        assert_eq!(result.content, "content");
+        assert!(matter
+            .parse("---\nabc = \"xyz\"\n---\ncontent")
+            .data
+            .is_none());
+
+        let matter = Matter::<YAML>::hugo();
+        let result = matter.parse("---\nabc: xyz\n---\ncontent");
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+    }
+
+    #[test]
+    fn test_parse_with_context() {
+        struct Dialect;
+        impl Engine for Dialect {
+            fn parse(content: &str) -> Pod {
+                YAML::parse(content)
+            }
+
+            fn parse_with_context(content: &str, ctx: &MatterContext) -> Pod {
+                match ctx.delimiter {
+                    "+++" => TOML::parse(content),
+                    _ => Self::parse(content),
+                }
+            }
+        }
+
+        let mut matter: Matter<Dialect> = Matter::new();
+        let result = matter.parse("---\nabc: xyz\n---\ncontent");
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        matter.delimiter = "+++".to_string();
+        let result = matter.parse("+++\nabc = \"xyz\"\n+++\ncontent");
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+        let result = matter.parse_with_engine("+++\nabc = \"xyz\"\n+++\ncontent", &Dialect);
+        assert_eq!(result.data.unwrap()["abc"], Pod::String("xyz".into()));
+    }
+
+    #[test]
+    fn test_format() {
+        let matter: Matter<YAML> = Matter::new();
+        let result = matter.parse("---\nabc: xyz\n---\ncontent");
+        assert_eq!(result.format, Some("yaml"));
+        let result = matter.parse("no front matter");
+        assert_eq!(
+            result.format, None,
+            "should get no format without front matter"
+        );
+        let matter: Matter<TOML> = Matter::new();
+        let result = matter.parse("---\nabc = \"xyz\"\n---\ncontent");
+        assert_eq!(result.format, Some("toml"));
+    }
+
     #[test]
     fn test_whitespace_without_frontmatter() {
         let matter: Matter<YAML> = Matter::new();
@@ -471,5 +2677,93 @@ field2 = [3.14, 42]
         );
 
         assert_eq!(result.excerpt.unwrap(), "    An excerpt".to_string());
+
+        let mut matter: Matter<YAML> = Matter::no_excerpt();
+        let result = matter.parse(raw);
+        assert_eq!(result.content, raw);
+        assert!(
+            result.excerpt.is_none(),
+            "should only detect explicit excerpts"
+        );
+
+        matter.excerpt_delimiter = Some("---".to_string());
+        let result = matter.parse(raw);
+        assert_eq!(result.excerpt.as_deref(), Some("    An excerpt"));
+    }
+
+    #[test]
+    fn test_alternate_delimiters() {
+        let mut matter: Matter<YAML> = Matter::new();
+        matter.alternate_delimiters = vec!["+++".to_string(), "...".to_string()];
+        for (input, title) in [
+            ("---\ntitle: dashes\n---\nBody", "dashes"),
+            ("+++\ntitle: pluses\n+++\nBody", "pluses"),
+            ("...\ntitle: dots\n...\nBody", "dots"),
+        ] {
+            let result = matter.try_parse(input).unwrap();
+            assert_eq!(
+                result.data.unwrap()["title"],
+                Pod::String(title.to_string())
+            );
+            assert_eq!(result.content, "Body");
+        }
+
+        // The opening delimiter is the only one closing the front matter
+        let input = "+++\ntitle: pluses\n---\nBody";
+        assert_eq!(matter.try_parse(input), Err(Error::UnterminatedFrontMatter));
+        let result = matter.parse("---\ntitle: dashes\n+++\nBody");
+        assert!(
+            result.data.is_none(),
+            "should not close on another delimiter"
+        );
+
+        // The alternate delimiter stands in for the delimiter as the excerpt delimiter as well
+        let result = matter.parse("+++\ntitle: pluses\n+++\nIntro\n+++\nBody");
+        assert_eq!(result.excerpt.as_deref(), Some("Intro"));
+        let result = matter.parse("Intro\n+++\nBody");
+        assert!(
+            result.excerpt.is_none(),
+            "should only look for the delimiter"
+        );
+
+        matter.position = MatterPosition::Trailing;
+        assert!(matter
+            .parse("Body\n+++\ntitle: pluses\n+++\n")
+            .data
+            .is_none());
+    }
+
+    #[test]
+    fn test_excerpt_delimiter_in_code_block() {
+        let matter: Matter<YAML> = Matter::new();
+        let input = "---\ntitle: Home\n---\nSee:\n```yaml\n---\nkey: value\n```\nIntro\n---\nBody";
+        let result = matter.parse(input);
+        assert_eq!(
+            result.excerpt.as_deref(),
+            Some("See:\n```yaml\n---\nkey: value\n```\nIntro")
+        );
+        assert_eq!(
+            result.content,
+            "See:\n```yaml\n---\nkey: value\n```\nIntro\n---\nBody"
+        );
+
+        // Only a fence of the same kind, at least as long, closes the block
+        let input = "````\n```\n---\n~~~\n````\nIntro\n---\nBody";
+        let result = matter.parse(input);
+        assert_eq!(
+            result.excerpt.as_deref(),
+            Some("````\n```\n---\n~~~\n````\nIntro")
+        );
+
+        // Without a closing fence the block runs to the end, leaving no excerpt
+        let result = matter.parse("~~~\n---\nBody");
+        assert!(result.excerpt.is_none(), "should not find the delimiter");
+        assert_eq!(result.content, "~~~\n---\nBody");
+
+        // Neither inline code nor indented fences open a block
+        let result = matter.parse("```a` b```\nIntro\n---\nBody");
+        assert_eq!(result.excerpt.as_deref(), Some("```a` b```\nIntro"));
+        let result = matter.parse("    ```\nIntro\n---\nBody");
+        assert_eq!(result.excerpt.as_deref(), Some("    ```\nIntro"));
     }
 }
@@ -1,6 +1,7 @@
 use crate::engine::yaml::YAML;
 use crate::entity::{ParsedEntity, ParsedEntityStruct};
 use crate::matter::Matter;
+use crate::Error;
 use serde::de::DeserializeOwned;
 use std::fs;
 use std::path::Path;
@@ -17,19 +18,15 @@ fn get_fixtures(file_name: &str) -> impl AsRef<Path> {
     root_dir
 }
 
-fn read_content(file_name: &str) -> String {
-    let path = get_fixtures(file_name);
-    fs::read_to_string(path).expect("Cannot read")
-}
-
 fn matter_yaml(file_name: &str) -> ParsedEntity {
-    let content = read_content(file_name);
     let matter: Matter<YAML> = Matter::new();
-    matter.parse(&content)
+    matter
+        .parse_file(get_fixtures(file_name))
+        .expect("Cannot read")
 }
 
 fn matter_yaml_struct<D: DeserializeOwned>(file_name: &str) -> Option<ParsedEntityStruct<D>> {
-    let content = read_content(file_name);
+    let content = fs::read_to_string(get_fixtures(file_name)).expect("Cannot read");
     let matter: Matter<YAML> = Matter::new();
     matter.parse_with_struct(&content)
 }
@@ -53,6 +50,33 @@ fn test_basic() {
         "should get content as \"this is content.\""
     );
 }
+#[test]
+fn test_parse_file_not_found() {
+    let matter: Matter<YAML> = Matter::new();
+    let result = matter.parse_file(get_fixtures("does-not-exist.md"));
+    assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_parse_bytes() {
+    let matter: Matter<YAML> = Matter::new();
+    let content = fs::read(get_fixtures("basic.txt")).unwrap();
+    let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+    with_bom.extend_from_slice(&content);
+    let result = matter.parse_bytes(&with_bom).unwrap();
+    assert_eq!(
+        result,
+        matter.parse_file(get_fixtures("basic.txt")).unwrap()
+    );
+
+    let mut invalid = content.clone();
+    invalid.extend_from_slice(b"\nLatin-1: caf\xE9");
+    assert_eq!(
+        matter.parse_bytes(&invalid),
+        Err(Error::InvalidUtf8(content.len() + 13))
+    );
+}
+
 #[test]
 fn test_parse_empty() {
     let result = matter_yaml("empty.md");
@@ -86,7 +110,10 @@ fn test_parse_complex_yaml_front_matter() {
         "should get front matter as {data_expected:?}"
     );
     assert!(!result.content.is_empty(), "should get content");
-    assert!(result.excerpt.is_some(), "should get excerpt")
+    assert!(
+        result.excerpt.is_none(),
+        "should not get an excerpt out of the delimiters in code blocks"
+    )
 }
 
 #[test]
@@ -104,12 +131,6 @@ fn test_parse_no_matter() {
 
 #[test]
 fn test_all_matter() {
-    #[derive(serde::Deserialize, PartialEq, Debug)]
-    struct FrontMatter {
-        one: String,
-        two: String,
-        three: String,
-    }
     let result = matter_yaml("all.yaml");
     assert!(
         result.data.is_none(),
@@ -1,2 +1,3 @@
+mod deserializer;
 pub mod error;
 pub mod pod;
@@ -0,0 +1,305 @@
+use crate::value::error::Error;
+use crate::Pod;
+use serde::de::value::{MapAccessDeserializer, SeqDeserializer, StrDeserializer};
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::{iter, slice};
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::deserialize_error(msg.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pod {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Pod, D::Error> {
+        deserializer.deserialize_any(PodVisitor)
+    }
+}
+
+/// Builds a `Pod` out of whatever a format holds. Map keys which are not strings are stringified,
+/// and so are integers too large for `Pod::Integer`, to keep them exact.
+struct PodVisitor;
+
+impl<'de> Visitor<'de> for PodVisitor {
+    type Value = Pod;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Pod, E> {
+        Ok(Pod::Boolean(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Pod, E> {
+        Ok(Pod::Integer(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Pod, E> {
+        Ok(i64::try_from(value).map_or_else(|_| Pod::String(value.to_string()), Pod::Integer))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Pod, E> {
+        Ok(Pod::Float(value))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Pod, E> {
+        Ok(Pod::String(value.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Pod, E> {
+        Ok(Pod::String(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Pod, E> {
+        Ok(Pod::Bytes(value.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Pod, E> {
+        Ok(Pod::Bytes(value))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Pod, E> {
+        Ok(Pod::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Pod, D::Error> {
+        <Pod as Deserialize>::deserialize(deserializer)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Pod, E> {
+        Ok(Pod::Null)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Pod, D::Error> {
+        <Pod as Deserialize>::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Pod, A::Error> {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(item) = seq.next_element()? {
+            vec.push(item);
+        }
+        Ok(Pod::Array(vec))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Pod, A::Error> {
+        let mut hash = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some((key, value)) = map.next_entry::<Pod, Pod>()? {
+            hash.insert(key.as_string_lossy(), value);
+        }
+        Ok(Pod::Hash(hash))
+    }
+}
+
+/// A [`Deserializer`](serde::Deserializer) reading straight from a borrowed `Pod`.
+///
+/// When `lenient` is set, integers, floats and booleans are accepted wherever a string is
+/// expected, and stringified.
+///
+/// Errors deserializing a nested value are prefixed with the path to it, e.g.
+/// `at analytics.alexa: invalid type: ...`.
+pub(crate) struct PodDeserializer<'a> {
+    pod: &'a Pod,
+    lenient: bool,
+    path: String,
+}
+
+impl<'a> PodDeserializer<'a> {
+    pub(crate) fn new(pod: &'a Pod, lenient: bool) -> Self {
+        PodDeserializer {
+            pod,
+            lenient,
+            path: String::new(),
+        }
+    }
+
+    /// Deserializes `pod`, found at `segment` within the value at `path`, with `seed`.
+    fn nested<'de, T: DeserializeSeed<'de>>(
+        seed: T,
+        pod: &'a Pod,
+        lenient: bool,
+        path: &str,
+        segment: &str,
+    ) -> Result<T::Value, Error> {
+        let path = if path.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{path}.{segment}")
+        };
+        let deserializer = PodDeserializer {
+            pod,
+            lenient,
+            path: path.clone(),
+        };
+        seed.deserialize(deserializer).map_err(|err| match err {
+            // Only the innermost value adds the path, which is the full one
+            Error::DeserializeError(msg) if !msg.starts_with("at ") => {
+                Error::DeserializeError(format!("at {path}: {msg}"))
+            }
+            err => err,
+        })
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for PodDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match *self.pod {
+            Pod::Null => visitor.visit_unit(),
+            Pod::String(ref value) => visitor.visit_str(value),
+            Pod::Integer(value) => visitor.visit_i64(value),
+            Pod::Float(value) => visitor.visit_f64(value),
+            Pod::Boolean(value) => visitor.visit_bool(value),
+            Pod::Array(ref value) => visitor.visit_seq(PodSeqAccess {
+                iter: value.iter().enumerate(),
+                lenient: self.lenient,
+                path: &self.path,
+            }),
+            Pod::Hash(ref value) => visitor.visit_map(PodMapAccess {
+                iter: value.iter(),
+                entry: None,
+                lenient: self.lenient,
+                path: &self.path,
+            }),
+            Pod::Bytes(ref value) => visitor.visit_bytes(value),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match *self.pod {
+            Pod::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match *self.pod {
+            Pod::Integer(_) | Pod::Float(_) | Pod::Boolean(_) if self.lenient => {
+                visitor.visit_string(self.pod.as_string_lossy())
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match *self.pod {
+            // Lets bytes fill a `Vec<u8>`, which expects a sequence rather than bytes
+            Pod::Bytes(ref value) => visitor.visit_seq(SeqDeserializer::new(value.iter().copied())),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match *self.pod {
+            Pod::String(ref value) => {
+                let variant: StrDeserializer<Error> = value.as_str().into_deserializer();
+                visitor.visit_enum(variant)
+            }
+            Pod::Hash(ref value) if value.len() == 1 => {
+                visitor.visit_enum(MapAccessDeserializer::new(PodMapAccess {
+                    iter: value.iter(),
+                    entry: None,
+                    lenient: self.lenient,
+                    path: &self.path,
+                }))
+            }
+            _ => Err(self.pod.type_mismatch("String or Hash with a single key")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf unit
+        unit_struct tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct PodSeqAccess<'a> {
+    iter: iter::Enumerate<slice::Iter<'a, Pod>>,
+    lenient: bool,
+    path: &'a str,
+}
+
+impl<'de, 'a> SeqAccess<'de> for PodSeqAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        match self.iter.next() {
+            Some((i, pod)) => {
+                PodDeserializer::nested(seed, pod, self.lenient, self.path, &i.to_string())
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct PodMapAccess<'a> {
+    iter: hash_map::Iter<'a, String, Pod>,
+    entry: Option<(&'a String, &'a Pod)>,
+    lenient: bool,
+    path: &'a str,
+}
+
+impl<'de, 'a> MapAccess<'de> for PodMapAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.iter.next() {
+            Some((key, value)) => {
+                self.entry = Some((key, value));
+                let key: StrDeserializer<Error> = key.as_str().into_deserializer();
+                seed.deserialize(key).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let (key, value) = self
+            .entry
+            .take()
+            .ok_or_else(|| Error::deserialize_error("value is missing".to_string()))?;
+        PodDeserializer::nested(seed, value, self.lenient, self.path, key)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
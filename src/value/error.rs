@@ -1,10 +1,32 @@
 use std::error;
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Error {
     TypeError(String),
     DeserializeError(String),
+    SerializeError(String),
+    /// The opening front matter delimiter was found, but the closing one was not.
+    UnterminatedFrontMatter,
+    /// Two collections were expected to have the same length, but had the contained lengths.
+    LengthMismatch(usize, usize),
+    /// The input is not valid UTF-8 past the contained byte offset.
+    InvalidUtf8(usize),
+    /// The input is not valid in the encoding it was declared in past the contained byte offset.
+    InvalidEncoding(usize),
+    /// The front matter is larger than the contained limit in bytes.
+    MatterTooLarge(usize),
+    /// The engine failed to parse the front matter, for the contained reason.
+    InvalidFrontMatter(String),
+    /// The engine failed to parse the front matter, for the contained reason, at the contained
+    /// line and column within it, both counted from 1.
+    SyntaxError {
+        msg: String,
+        line: usize,
+        column: usize,
+    },
+    /// A value was expected, but there is none, e.g. a document without front matter.
+    ValueMissingError,
 }
 
 impl Error {
@@ -12,9 +34,25 @@ impl Error {
         Error::TypeError(expected.into())
     }
 
+    pub fn type_mismatch(expected: &str, found: &str) -> Self {
+        Error::TypeError(format!("{expected}, found: {found}"))
+    }
+
     pub fn deserialize_error(msg: String) -> Self {
         Error::DeserializeError(msg)
     }
+
+    pub fn serialize_error(msg: String) -> Self {
+        Error::SerializeError(msg)
+    }
+
+    pub fn invalid_front_matter(msg: String) -> Self {
+        Error::InvalidFrontMatter(msg)
+    }
+
+    pub fn length_mismatch(left: usize, right: usize) -> Self {
+        Error::LengthMismatch(left, right)
+    }
 }
 
 impl Display for Error {
@@ -24,6 +62,19 @@ impl Display for Error {
         match *self {
             TypeError(ref s) => write!(f, "Type error, expected: {s}"),
             DeserializeError(ref s) => write!(f, "Deserialize error: {s}"),
+            SerializeError(ref s) => write!(f, "Serialize error: {s}"),
+            UnterminatedFrontMatter => write!(f, "Unterminated front matter"),
+            LengthMismatch(left, right) => write!(f, "Length mismatch: {left} and {right}"),
+            InvalidUtf8(offset) => write!(f, "Invalid UTF-8 after byte {offset}"),
+            InvalidEncoding(offset) => write!(f, "Invalid encoding after byte {offset}"),
+            MatterTooLarge(max) => write!(f, "Front matter larger than {max} bytes"),
+            InvalidFrontMatter(ref s) => write!(f, "Invalid front matter: {s}"),
+            SyntaxError {
+                ref msg,
+                line,
+                column,
+            } => write!(f, "Syntax error at line {line} column {column}: {msg}"),
+            ValueMissingError => write!(f, "Value missing"),
         }
     }
 }
@@ -35,6 +86,15 @@ impl error::Error for Error {
         match *self {
             TypeError(_) => "Type error",
             DeserializeError(_) => "Deserialize error",
+            SerializeError(_) => "Serialize error",
+            UnterminatedFrontMatter => "Unterminated front matter",
+            LengthMismatch(..) => "Length mismatch",
+            InvalidUtf8(_) => "Invalid UTF-8",
+            InvalidEncoding(_) => "Invalid encoding",
+            MatterTooLarge(_) => "Front matter too large",
+            InvalidFrontMatter(_) => "Invalid front matter",
+            SyntaxError { .. } => "Syntax error",
+            ValueMissingError => "Value missing",
         }
     }
 }
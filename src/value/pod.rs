@@ -1,8 +1,15 @@
+use crate::value::deserializer::PodDeserializer;
 use crate::value::error::Error;
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, DeserializeSeed};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::ffi::OsString;
+use std::fmt;
+use std::iter::FromIterator;
 use std::mem;
 use std::ops::{Index, IndexMut};
+use std::slice;
 
 type IResult<T> = Result<T, Error>;
 
@@ -11,7 +18,14 @@ type IResult<T> = Result<T, Error>;
 /// Any [`Engine`](crate::engine::Engine) has to convert the data represented by the format into a
 /// `Pod`. This ensures we can use the parsed data similarly, regardless of the format it is parsed
 /// from.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Two `Pod::Float`s holding `NaN` compare equal, so that structurally identical pods always do.
+///
+/// Scalars of the same kind are ordered as well: numbers numerically, strings lexically, and
+/// `false` before `true`. Any other pair is incomparable unless equal, including containers and a
+/// number equal to one of the other numeric type, which `==` tells apart.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Pod {
     Null,
     String(String),
@@ -20,22 +34,384 @@ pub enum Pod {
     Boolean(bool),
     Array(Vec<Pod>),
     Hash(HashMap<String, Pod>),
+    /// Binary data. Deserialized as bytes, which fill a `Vec<u8>`, or a `String` when valid UTF-8.
+    /// Converted into a base64 encoded string by [`to_json`](Pod::to_json).
+    Bytes(Vec<u8>),
+}
+
+/// A step on the path to a value nested in a `Pod`, as passed to [`Pod::walk`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// The key of a `Pod::Hash` entry.
+    Key(String),
+    /// The index of a `Pod::Array` element.
+    Index(usize),
+}
+
+/// The variants of a `Pod`, without their values, as passed to [`Pod::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PodType {
+    Null,
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Array,
+    Hash,
+    Bytes,
+}
+
+/// A key of a `Pod::Hash` which does not match the spec passed to [`Pod::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The key is absent.
+    Missing(String),
+    /// The value of the key is of another type than expected.
+    Mismatch {
+        key: String,
+        expected: PodType,
+        found: PodType,
+    },
 }
 
 static NULL: Pod = Pod::Null;
 
+impl PartialEq for Pod {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Pod::Null, Pod::Null) => true,
+            (Pod::String(a), Pod::String(b)) => a == b,
+            (Pod::Integer(a), Pod::Integer(b)) => a == b,
+            (Pod::Float(a), Pod::Float(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Pod::Boolean(a), Pod::Boolean(b)) => a == b,
+            (Pod::Array(a), Pod::Array(b)) => a == b,
+            (Pod::Hash(a), Pod::Hash(b)) => a == b,
+            (Pod::Bytes(a), Pod::Bytes(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl PartialOrd for Pod {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Pod::String(a), Pod::String(b)) => Some(a.cmp(b)),
+            (Pod::Integer(a), Pod::Integer(b)) => Some(a.cmp(b)),
+            (Pod::Float(a), Pod::Float(b)) if a.is_nan() && b.is_nan() => Some(Ordering::Equal),
+            (Pod::Float(a), Pod::Float(b)) => a.partial_cmp(b),
+            (Pod::Integer(a), Pod::Float(b)) => match (*a as f64).partial_cmp(b)? {
+                Ordering::Equal => None,
+                ordering => Some(ordering),
+            },
+            (Pod::Float(_), Pod::Integer(_)) => other.partial_cmp(self).map(Ordering::reverse),
+            (Pod::Boolean(a), Pod::Boolean(b)) => Some(a.cmp(b)),
+            _ if self == other => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
 impl Pod {
     /// Deserialize a `Pod` into any struct that implements
     /// [`Deserialize`](https://docs.rs/serde/1.0.127/serde/trait.Deserialize.html).
     ///
-    /// **Note**: The function coerces `self` into a
-    /// [`serde_json::Value`](https://docs.rs/serde_json/1.0.66/serde_json/enum.Value.html) in
-    /// order to work around implementing a custom `Deserializer` for `Pod`.
+    /// Structs are usually deserialized from a `Pod::Hash`, by field name. They can be
+    /// deserialized from a `Pod::Array` as well, by field order, which is the only way for tuple
+    /// structs.
+    ///
+    /// Errors name the path to the offending value, e.g. `at analytics.alexa: invalid type: ...`.
     pub fn deserialize<T: DeserializeOwned>(&self) -> json::Result<T> {
-        use json::{from_value, Value};
-        let value: Value = self.clone().into();
-        let ret: T = from_value(value)?;
-        Ok(ret)
+        T::deserialize(PodDeserializer::new(self, false)).map_err(|err| match err {
+            Error::DeserializeError(msg) => serde::de::Error::custom(msg),
+            err => serde::de::Error::custom(err),
+        })
+    }
+
+    /// Deserialize a `Pod` into any struct, like [`deserialize`](Pod::deserialize), but
+    /// integers, floats and booleans are stringified wherever the struct expects a string.
+    ///
+    /// Errors name the path to the offending value, e.g. `at analytics.alexa: invalid type: ...`.
+    pub fn deserialize_lenient<T: DeserializeOwned>(&self) -> IResult<T> {
+        T::deserialize(PodDeserializer::new(self, true))
+    }
+
+    /// Deserializes a `Pod` with a [`DeserializeSeed`](serde::de::DeserializeSeed), which carries
+    /// state through the deserialization, e.g. to intern strings.
+    ///
+    /// Errors name the path to the offending value, like those of
+    /// [`deserialize_lenient`](Pod::deserialize_lenient).
+    pub fn deserialize_with_seed<'de, S: DeserializeSeed<'de>>(
+        &self,
+        seed: S,
+    ) -> IResult<S::Value> {
+        seed.deserialize(PodDeserializer::new(self, false))
+    }
+
+    /// Deserializes the elements of a `Pod::Array` into a `Vec<T>`, e.g. `Vec<String>` out of a
+    /// list of tags. Fails with a type error for any other type.
+    ///
+    /// Errors name the index of the offending element, e.g. `at 1: invalid type: ...`.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Pod;
+    /// let pod = Pod::Array(vec![Pod::String("rust".into()), Pod::String("yaml".into())]);
+    ///
+    /// assert_eq!(pod.to_vec_of::<String>().unwrap(), vec!["rust", "yaml"]);
+    /// ```
+    pub fn to_vec_of<T: DeserializeOwned>(&self) -> IResult<Vec<T>> {
+        if !self.is_array() {
+            return Err(self.type_mismatch("Array"));
+        }
+        <Vec<T> as serde::Deserialize>::deserialize(PodDeserializer::new(self, false))
+    }
+
+    /// Looks up `key` in a `Pod::Hash` and deserializes its value into `T`, without the panic
+    /// indexing brings along. Fails with [`Error::ValueMissingError`] when the key is absent, and
+    /// with a type error for other types.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Error, Pod};
+    /// let mut pod = Pod::new_hash();
+    /// pod["title"] = Pod::String("Home".into());
+    ///
+    /// assert_eq!(pod.try_get_as::<String>("title").unwrap(), "Home");
+    /// assert_eq!(pod.try_get_as::<String>("author"), Err(Error::ValueMissingError));
+    /// ```
+    pub fn try_get_as<T: DeserializeOwned>(&self, key: &str) -> IResult<T> {
+        match *self {
+            Pod::Hash(ref hash) => match hash.get(key) {
+                Some(value) => T::deserialize(PodDeserializer::new(value, false)),
+                None => Err(Error::ValueMissingError),
+            },
+            _ => Err(self.type_mismatch("Hash")),
+        }
+    }
+
+    /// Deserialize a `Pod` into any struct, like [`deserialize`](Pod::deserialize), but with
+    /// values from environment variables starting with `prefix` merged over it first.
+    ///
+    /// See [`from_env_prefix`](Pod::from_env_prefix) for how the variables are mapped to keys.
+    pub fn deserialize_with_env_overrides<T: DeserializeOwned>(&self, prefix: &str) -> IResult<T> {
+        let mut pod = self.clone();
+        pod.merge(Pod::from_env_prefix(prefix));
+        Ok(pod.deserialize()?)
+    }
+
+    /// Collects every environment variable named `{prefix}_{KEY}` into a `Pod::Hash`, keyed by the
+    /// lowercased `KEY`.
+    ///
+    /// Values are typed the way [`coerce_scalars`](Pod::coerce_scalars) types strings. Variables
+    /// whose name or value is not valid Unicode are skipped.
+    pub fn from_env_prefix(prefix: &str) -> Pod {
+        Pod::from_prefixed_vars(prefix, std::env::vars_os())
+    }
+
+    fn from_prefixed_vars<I>(prefix: &str, vars: I) -> Pod
+    where
+        I: IntoIterator<Item = (OsString, OsString)>,
+    {
+        let prefix = format!("{prefix}_");
+        let mut hash = HashMap::new();
+        for (key, value) in vars {
+            let (key, value) = match (key.into_string(), value.into_string()) {
+                (Ok(key), Ok(value)) => (key, value),
+                _ => continue,
+            };
+            if let Some(key) = key.strip_prefix(&prefix) {
+                let mut value = Pod::String(value);
+                value.coerce_scalars();
+                hash.insert(key.to_lowercase(), value);
+            }
+        }
+        Pod::Hash(hash)
+    }
+
+    /// Recursively turns every `Pod::String` that reads as a boolean, an integer or a float into
+    /// the corresponding type, for engines which yield nothing but strings.
+    ///
+    /// Only `true` and `false` count as booleans. Strings which might not be meant as numbers, like
+    /// `042`, `+1`, `.5` or `inf`, are left as they are.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Pod;
+    /// let mut pod = Pod::Array(vec![Pod::String("42".into()), Pod::String("042".into())]);
+    /// pod.coerce_scalars();
+    ///
+    /// assert_eq!(pod, Pod::Array(vec![Pod::Integer(42), Pod::String("042".into())]));
+    /// ```
+    pub fn coerce_scalars(&mut self) {
+        match *self {
+            Pod::String(ref value) => {
+                if let Some(pod) = coerce_scalar(value) {
+                    *self = pod;
+                }
+            }
+            Pod::Array(ref mut vec) => vec.iter_mut().for_each(Pod::coerce_scalars),
+            Pod::Hash(ref mut hash) => hash.values_mut().for_each(Pod::coerce_scalars),
+            _ => {}
+        }
+    }
+
+    /// Merges `other` into `self`. Keys of nested `Pod::Hash`es are merged recursively, any other
+    /// value of `other` replaces the one in `self`.
+    pub fn merge(&mut self, other: Pod) {
+        match (self, other) {
+            (Pod::Hash(ref mut hash), Pod::Hash(other)) => {
+                for (key, value) in other.into_iter() {
+                    match hash.get_mut(&key) {
+                        Some(existing) => existing.merge(value),
+                        None => {
+                            hash.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (this, other) => *this = other,
+        }
+    }
+
+    /// Fills in the keys of `defaults` missing from `self`, recursing into `Pod::Hash`es both
+    /// hold. Unlike [`merge`](Pod::merge), values already in `self` are left untouched, even when
+    /// null. Has no effect unless both are a `Pod::Hash`.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Pod;
+    /// let mut pod = Pod::new_hash();
+    /// pod["title"] = Pod::String("Home".into());
+    /// let mut defaults = Pod::new_hash();
+    /// defaults["title"] = Pod::String("Untitled".into());
+    /// defaults["layout"] = Pod::String("post".into());
+    /// pod.fill_defaults(&defaults);
+    ///
+    /// assert_eq!(pod["title"], Pod::String("Home".into()));
+    /// assert_eq!(pod["layout"], Pod::String("post".into()));
+    /// ```
+    pub fn fill_defaults(&mut self, defaults: &Pod) {
+        if let (Pod::Hash(ref mut hash), Pod::Hash(defaults)) = (self, defaults) {
+            for (key, default) in defaults {
+                match hash.get_mut(key) {
+                    Some(existing) => existing.fill_defaults(default),
+                    None => {
+                        hash.insert(key.clone(), default.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Converts a `Pod` into a [`serde_json::Value`](https://docs.rs/serde_json/1/serde_json/enum.Value.html).
+    /// Hash keys are inserted in sorted order, and `Pod::Bytes` becomes a base64 encoded string.
+    pub fn to_json(&self) -> json::Value {
+        self.clone().into()
+    }
+
+    /// Converts a [`serde_json::Value`](https://docs.rs/serde_json/1/serde_json/enum.Value.html)
+    /// into a `Pod`, the same way the [`JSON`](crate::engine::JSON) engine does.
+    pub fn from_json(value: &json::Value) -> Pod {
+        value.into()
+    }
+
+    /// Serializes a `Pod` into canonical JSON: keys of every hash sorted, and no insignificant
+    /// whitespace. Equal pods always produce identical strings.
+    pub fn to_canonical_json(&self) -> IResult<String> {
+        let mut out = String::new();
+        write_canonical_json(&self.clone().into(), &mut out)?;
+        Ok(out)
+    }
+
+    /// Compares two pods like `==` does, except that arrays are compared as multisets: elements
+    /// may be in any order, at any depth.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Pod;
+    /// let a = Pod::Array(vec![Pod::Integer(1), Pod::Integer(2)]);
+    /// let b = Pod::Array(vec![Pod::Integer(2), Pod::Integer(1)]);
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_unordered(&b));
+    /// ```
+    pub fn eq_unordered(&self, other: &Pod) -> bool {
+        match (self, other) {
+            (Pod::Array(a), Pod::Array(b)) => {
+                if a.len() != b.len() {
+                    return false;
+                }
+                let mut matched = vec![false; b.len()];
+                a.iter().all(|item| {
+                    let found = b
+                        .iter()
+                        .enumerate()
+                        .position(|(i, other)| !matched[i] && item.eq_unordered(other));
+                    match found {
+                        Some(i) => {
+                            matched[i] = true;
+                            true
+                        }
+                        None => false,
+                    }
+                })
+            }
+            (Pod::Hash(a), Pod::Hash(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(key, value)| {
+                        b.get(key).is_some_and(|other| value.eq_unordered(other))
+                    })
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Like `==`, but `Pod::Integer` and `Pod::Float` holding the same number are equal as
+    /// well, also when nested in collections. Useful to compare data parsed from formats which
+    /// disagree on whether `1.0` is an integer.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::Pod;
+    /// assert_ne!(Pod::Integer(1), Pod::Float(1.0));
+    /// assert!(Pod::Integer(1).num_eq(&Pod::Float(1.0)));
+    /// assert!(!Pod::Integer(1).num_eq(&Pod::Float(1.5)));
+    /// ```
+    pub fn num_eq(&self, other: &Pod) -> bool {
+        match (self, other) {
+            (Pod::Integer(a), Pod::Float(b)) | (Pod::Float(b), Pod::Integer(a)) => {
+                // Every integral float within range converts exactly, unlike the other way round
+                b.fract() == 0.0 && *b >= i64::MIN as f64 && *b < i64::MAX as f64 && *b as i64 == *a
+            }
+            (Pod::Array(a), Pod::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.num_eq(b))
+            }
+            (Pod::Hash(a), Pod::Hash(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(key, value)| b.get(key).is_some_and(|other| value.num_eq(other)))
+            }
+            _ => self == other,
+        }
     }
 
     pub fn new_array() -> Pod {
@@ -46,6 +422,16 @@ impl Pod {
         Pod::Hash(HashMap::new())
     }
 
+    /// Creates an empty `Pod::Array` with room for at least `capacity` elements.
+    pub fn array_with_capacity(capacity: usize) -> Pod {
+        Pod::Array(Vec::with_capacity(capacity))
+    }
+
+    /// Creates an empty `Pod::Hash` with room for at least `capacity` entries.
+    pub fn hash_with_capacity(capacity: usize) -> Pod {
+        Pod::Hash(HashMap::with_capacity(capacity))
+    }
+
     /// Pushes a new value into `Pod::Array`.
     pub fn push<T>(&mut self, value: T) -> IResult<()>
     where
@@ -56,7 +442,7 @@ impl Pod {
                 vec.push(value.into());
                 Ok(())
             }
-            _ => Err(Error::type_error("Array")),
+            _ => Err(self.type_mismatch("Array")),
         }
     }
 
@@ -78,7 +464,7 @@ impl Pod {
                 hash.insert(key, val.into());
                 Ok(())
             }
-            _ => Err(Error::type_error("Hash")),
+            _ => Err(self.type_mismatch("Hash")),
         }
     }
 
@@ -90,157 +476,844 @@ impl Pod {
         }
     }
 
-    /// Takes the ownership of Pod
-    pub fn take(&mut self) -> Pod {
-        mem::replace(self, Pod::Null)
+    /// Moves the value of `from` in Pod::Hash under `to`, overriding any value there. Returns
+    /// whether `from` was present, doing nothing otherwise or for other types.
+    pub fn rename_key(&mut self, from: &str, to: &str) -> bool {
+        match *self {
+            Pod::Hash(ref mut hash) => match hash.remove(from) {
+                Some(value) => {
+                    hash.insert(to.to_string(), value);
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
     }
 
-    /// Returns length of Pod::Array and Pod::Hash, 0 as default for other types.
-    pub fn len(&self) -> usize {
+    /// Follows `path` into nested hashes and arrays, returning the value at its end, or `None` if
+    /// any segment does not match. The paths passed by [`walk`](Pod::walk) lead back to the
+    /// visited nodes.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{PathSegment, Pod};
+    /// let mut pod = Pod::new_hash();
+    /// pod["tags"] = Pod::Array(vec![Pod::String("rust".into())]);
+    /// let path = [PathSegment::Key("tags".into()), PathSegment::Index(0)];
+    ///
+    /// assert_eq!(pod.get_path(&path), Some(&Pod::String("rust".into())));
+    /// ```
+    pub fn get_path(&self, path: &[PathSegment]) -> Option<&Pod> {
+        path.iter()
+            .try_fold(self, |pod, segment| match (pod, segment) {
+                (Pod::Hash(hash), PathSegment::Key(key)) => hash.get(key),
+                (Pod::Array(vec), PathSegment::Index(i)) => vec.get(*i),
+                _ => None,
+            })
+    }
+
+    /// Looks up `key` in a `Pod::Hash`, ignoring ASCII case, e.g. `title` finds `Title`. An exact
+    /// match wins, and among several others the lowest key in byte order. Returns `None` for
+    /// other types.
+    ///
+    /// Unlike indexing, this walks every entry, so it takes O(n) time.
+    pub fn get_ci(&self, key: &str) -> Option<&Pod> {
         match *self {
-            Pod::Array(ref value) => value.len(),
-            Pod::Hash(ref value) => value.len(),
-            _ => 0,
+            Pod::Hash(ref hash) => hash.get(key).or_else(|| {
+                hash.iter()
+                    .filter(|(other, _)| other.eq_ignore_ascii_case(key))
+                    .min_by_key(|(other, _)| other.as_str())
+                    .map(|(_, value)| value)
+            }),
+            _ => None,
         }
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    /// Keeps only the entries of `Pod::Hash` for which `f` returns `true`. Does nothing for other
+    /// types.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str, &Pod) -> bool,
+    {
+        if let Pod::Hash(ref mut hash) = *self {
+            hash.retain(|key, value| f(key, value));
+        }
     }
 
-    pub fn as_string(&self) -> Result<String, Error> {
-        match *self {
-            Pod::String(ref value) => Ok(value.clone()),
-            _ => Err(Error::type_error("String")),
+    /// Keeps only the elements of `Pod::Array` for which `f` returns `true`. Does nothing for other
+    /// types.
+    pub fn retain_array<F>(&mut self, f: F)
+    where
+        F: FnMut(&Pod) -> bool,
+    {
+        if let Pod::Array(ref mut vec) = *self {
+            vec.retain(f);
         }
     }
 
-    pub fn as_i64(&self) -> Result<i64, Error> {
-        match *self {
-            Pod::Integer(ref value) => Ok(*value),
-            _ => Err(Error::type_error("Integer")),
+    /// Splices the elements of any `Pod::Array` nested in a `Pod::Array` into it, e.g. turning
+    /// `[[a], b, [c, [d]]]` into `[a, b, c, [d]]`. Other elements stay as they are, and other types
+    /// are left untouched.
+    pub fn flatten_one_level(&mut self) {
+        if let Pod::Array(ref mut vec) = *self {
+            let mut flat = Vec::with_capacity(vec.len());
+            for elem in vec.drain(..) {
+                match elem {
+                    Pod::Array(inner) => flat.extend(inner),
+                    elem => flat.push(elem),
+                }
+            }
+            *vec = flat;
         }
     }
 
-    pub fn as_f64(&self) -> Result<f64, Error> {
-        match *self {
-            Pod::Float(ref value) => Ok(*value),
-            _ => Err(Error::type_error("Float")),
+    /// Pairs up the elements of two `Pod::Array`s of equal length into a `Pod::Array` of
+    /// two-element `Pod::Array`s.
+    pub fn zip(&self, other: &Pod) -> IResult<Pod> {
+        match (self, other) {
+            (Pod::Array(a), Pod::Array(b)) if a.len() == b.len() => Ok(a
+                .iter()
+                .zip(b.iter())
+                .map(|(a, b)| Pod::Array(vec![a.clone(), b.clone()]))
+                .collect()),
+            (Pod::Array(a), Pod::Array(b)) => Err(Error::length_mismatch(a.len(), b.len())),
+            (Pod::Array(_), _) => Err(other.type_mismatch("Array")),
+            _ => Err(self.type_mismatch("Array")),
         }
     }
 
-    pub fn as_bool(&self) -> Result<bool, Error> {
+    /// Pairs up every element of a `Pod::Array` with its index, as a `Pod::Array` of two-element
+    /// `Pod::Array`s.
+    pub fn enumerate(&self) -> IResult<Pod> {
         match *self {
-            Pod::Boolean(ref value) => Ok(*value),
-            _ => Err(Error::type_error("Boolean")),
+            Pod::Array(ref vec) => Ok(vec
+                .iter()
+                .enumerate()
+                .map(|(i, item)| Pod::Array(vec![Pod::Integer(i as i64), item.clone()]))
+                .collect()),
+            _ => Err(self.type_mismatch("Array")),
         }
     }
 
-    pub fn as_vec(&self) -> Result<Vec<Pod>, Error> {
+    /// Returns the lowercase name of the variant, e.g. `"integer"`. Handy for error messages.
+    pub fn type_name(&self) -> &'static str {
         match *self {
-            Pod::Array(ref value) => Ok(value.clone()),
-            _ => Err(Error::type_error("Array")),
+            Pod::Null => "null",
+            Pod::String(_) => "string",
+            Pod::Integer(_) => "integer",
+            Pod::Float(_) => "float",
+            Pod::Boolean(_) => "boolean",
+            Pod::Array(_) => "array",
+            Pod::Hash(_) => "hash",
+            Pod::Bytes(_) => "bytes",
         }
     }
 
-    pub fn as_hashmap(&self) -> Result<HashMap<String, Pod>, Error> {
+    /// The variant of `self`, without its value.
+    pub fn pod_type(&self) -> PodType {
         match *self {
-            Pod::Hash(ref value) => Ok(value.clone()),
-            _ => Err(Error::type_error("Hash")),
+            Pod::Null => PodType::Null,
+            Pod::String(_) => PodType::String,
+            Pod::Integer(_) => PodType::Integer,
+            Pod::Float(_) => PodType::Float,
+            Pod::Boolean(_) => PodType::Boolean,
+            Pod::Array(_) => PodType::Array,
+            Pod::Hash(_) => PodType::Hash,
+            Pod::Bytes(_) => PodType::Bytes,
         }
     }
-}
 
-impl Into<String> for Pod {
-    fn into(self) -> String {
-        self.as_string().unwrap()
+    /// Checks that a `Pod::Hash` holds every key of `spec` with a value of the given type, e.g.
+    /// before deserializing it into a strict struct. Types have to match exactly, so a
+    /// `Pod::Integer` does not pass for [`PodType::Float`]. Other types hold no keys at all.
+    ///
+    /// Returns every key which does not match, rather than only the first.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{Pod, PodType, ValidationError};
+    /// let mut pod = Pod::new_hash();
+    /// pod["title"] = Pod::String("Home".into());
+    /// pod["weight"] = Pod::String("3".into());
+    /// let spec = [
+    ///     ("title", PodType::String),
+    ///     ("weight", PodType::Integer),
+    ///     ("tags", PodType::Array),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     pod.validate(&spec),
+    ///     Err(vec![
+    ///         ValidationError::Mismatch {
+    ///             key: "weight".into(),
+    ///             expected: PodType::Integer,
+    ///             found: PodType::String,
+    ///         },
+    ///         ValidationError::Missing("tags".into()),
+    ///     ])
+    /// );
+    /// ```
+    pub fn validate(&self, spec: &[(&str, PodType)]) -> Result<(), Vec<ValidationError>> {
+        let errors = spec
+            .iter()
+            .filter_map(|&(key, expected)| {
+                let value = match *self {
+                    Pod::Hash(ref hash) => hash.get(key),
+                    _ => None,
+                };
+                match value.map(Pod::pod_type) {
+                    None => Some(ValidationError::Missing(key.to_string())),
+                    Some(found) if found != expected => Some(ValidationError::Mismatch {
+                        key: key.to_string(),
+                        expected,
+                        found,
+                    }),
+                    Some(_) => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
     }
-}
 
-impl Into<i64> for Pod {
-    fn into(self) -> i64 {
-        self.as_i64().unwrap()
+    pub(crate) fn type_mismatch(&self, expected: &str) -> Error {
+        Error::type_mismatch(expected, self.type_name())
     }
-}
 
-impl Into<f64> for Pod {
-    fn into(self) -> f64 {
-        self.as_f64().unwrap()
+    /// Like `Display`, but quotes strings, as they are within a collection.
+    fn fmt_nested(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Pod::String(ref value) => write!(f, "{value:?}"),
+            Pod::Bytes(ref value) => write!(f, "{:?}", base64_encode(value)),
+            _ => fmt::Display::fmt(self, f),
+        }
     }
-}
 
-impl Into<bool> for Pod {
-    fn into(self) -> bool {
-        self.as_bool().unwrap()
+    /// Collapses nested hashes and arrays into a flat `Pod::Hash`, keyed by the path to each value
+    /// joined with dots, e.g. `{a: {b: [1]}}` becomes `{"a.b.0": 1}`.
+    ///
+    /// Empty collections are kept as values, and a scalar is keyed by an empty string.
+    pub fn flatten(&self) -> Pod {
+        self.flatten_with_separator(".")
     }
-}
 
-impl Into<Vec<Pod>> for Pod {
-    fn into(self) -> Vec<Pod> {
-        self.as_vec().unwrap()
-    }
-}
+    /// Like [`flatten`](Pod::flatten), but joins the path with `separator`.
+    pub fn flatten_with_separator(&self, separator: &str) -> Pod {
+        fn walk(pod: &Pod, path: String, separator: &str, out: &mut HashMap<String, Pod>) {
+            let join = |key: &str| {
+                if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}{separator}{key}")
+                }
+            };
+            match *pod {
+                Pod::Hash(ref hash) if !hash.is_empty() => {
+                    for (key, value) in hash.iter() {
+                        walk(value, join(key), separator, out);
+                    }
+                }
+                Pod::Array(ref vec) if !vec.is_empty() => {
+                    for (i, value) in vec.iter().enumerate() {
+                        walk(value, join(&i.to_string()), separator, out);
+                    }
+                }
+                _ => {
+                    out.insert(path, pod.clone());
+                }
+            }
+        }
 
-impl Into<HashMap<String, Pod>> for Pod {
-    fn into(self) -> HashMap<String, Pod> {
-        self.as_hashmap().unwrap()
+        let mut out = HashMap::new();
+        walk(self, String::new(), separator, &mut out);
+        Pod::Hash(out)
     }
-}
 
-impl From<i64> for Pod {
-    fn from(val: i64) -> Self {
-        Pod::Integer(val)
+    /// Visits every node of a `Pod` depth-first, along with the path leading to it: first the node
+    /// itself, then its children. Arrays are visited in order, and hashes in sorted key order. The
+    /// root is visited with an empty path.
+    ///
+    /// ## Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```rust
+    /// # use gray_matter::{PathSegment, Pod};
+    /// let mut pod = Pod::new_hash();
+    /// pod["tags"] = Pod::Array(vec![Pod::String("rust".into())]);
+    /// let mut scalars = vec![];
+    /// pod.walk(|path, node| {
+    ///     if let Pod::String(value) = node {
+    ///         scalars.push((path.to_vec(), value.clone()));
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(
+    ///     scalars,
+    ///     vec![(
+    ///         vec![PathSegment::Key("tags".into()), PathSegment::Index(0)],
+    ///         "rust".to_string()
+    ///     )]
+    /// );
+    /// ```
+    pub fn walk<F>(&self, mut f: F)
+    where
+        F: FnMut(&[PathSegment], &Pod),
+    {
+        fn visit<F>(pod: &Pod, path: &mut Vec<PathSegment>, f: &mut F)
+        where
+            F: FnMut(&[PathSegment], &Pod),
+        {
+            f(path, pod);
+            match *pod {
+                Pod::Array(ref vec) => {
+                    for (i, item) in vec.iter().enumerate() {
+                        path.push(PathSegment::Index(i));
+                        visit(item, path, f);
+                        path.pop();
+                    }
+                }
+                Pod::Hash(_) => {
+                    for (key, item) in pod.sorted_entries() {
+                        path.push(PathSegment::Key(key.to_string()));
+                        visit(item, path, f);
+                        path.pop();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        visit(self, &mut vec![], &mut f);
     }
-}
 
-impl From<f64> for Pod {
-    fn from(val: f64) -> Self {
-        Pod::Float(val)
+    /// Counts the values in `self`, including itself and every collection, e.g. 3 for `[1, 2]`.
+    /// Together with [`max_depth`](Pod::max_depth), this allows rejecting pathological front
+    /// matter from untrusted sources.
+    pub fn node_count(&self) -> usize {
+        1 + match *self {
+            Pod::Array(ref vec) => vec.iter().map(Pod::node_count).sum(),
+            Pod::Hash(ref hash) => hash.values().map(Pod::node_count).sum(),
+            _ => 0,
+        }
     }
-}
 
-impl From<String> for Pod {
-    fn from(val: String) -> Self {
-        Pod::String(val)
+    /// Counts the levels of collections nested in `self`: 0 for a scalar, 1 for a collection of
+    /// scalars, and so on, e.g. 2 for `{"tags": [1]}`.
+    pub fn max_depth(&self) -> usize {
+        match *self {
+            Pod::Array(ref vec) => 1 + vec.iter().map(Pod::max_depth).max().unwrap_or(0),
+            Pod::Hash(ref hash) => 1 + hash.values().map(Pod::max_depth).max().unwrap_or(0),
+            _ => 0,
+        }
     }
-}
 
-impl From<bool> for Pod {
-    fn from(val: bool) -> Self {
-        Pod::Boolean(val)
+    /// Returns the entries of a `Pod::Hash` sorted by key, or an empty list for any other type.
+    ///
+    /// The engines walk hashes in this order when stringifying, so equal pods always serialize
+    /// identically.
+    pub fn sorted_entries(&self) -> Vec<(&str, &Pod)> {
+        match *self {
+            Pod::Hash(ref value) => {
+                let mut entries = value
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value))
+                    .collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                entries
+            }
+            _ => vec![],
+        }
     }
-}
 
-impl From<Vec<Pod>> for Pod {
-    fn from(val: Vec<Pod>) -> Self {
-        Pod::Array(val)
+    /// Iterates over the elements of a `Pod::Array`, yielding nothing for any other type. This is
+    /// also what a `for` loop over a `&Pod` does, while the entries of a `Pod::Hash` are walked
+    /// with [`entries`](Pod::entries).
+    pub fn iter(&self) -> slice::Iter<'_, Pod> {
+        match *self {
+            Pod::Array(ref value) => value.iter(),
+            _ => [].iter(),
+        }
     }
-}
 
-impl From<HashMap<String, Pod>> for Pod {
-    fn from(val: HashMap<String, Pod>) -> Self {
-        Pod::Hash(val)
+    /// Iterates over the entries of a `Pod::Hash` in arbitrary order, yielding nothing for any
+    /// other type. See [`sorted_entries`](Pod::sorted_entries) for a stable order.
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &Pod)> {
+        match *self {
+            Pod::Hash(ref value) => Some(value.iter()),
+            _ => None,
+        }
+        .into_iter()
+        .flatten()
     }
-}
 
-impl Index<usize> for Pod {
-    type Output = Pod;
+    /// Takes the ownership of Pod
+    pub fn take(&mut self) -> Pod {
+        mem::replace(self, Pod::Null)
+    }
 
-    /// Easily access element of Pod::Array by usize index
-    fn index(&self, index: usize) -> &Self::Output {
+    /// Returns length of Pod::Array, Pod::Hash and Pod::Bytes, 0 as default for other types.
+    pub fn len(&self) -> usize {
         match *self {
-            Pod::Array(ref vec) => vec.get(index).unwrap_or(&NULL),
-            _ => &NULL,
+            Pod::Array(ref value) => value.len(),
+            Pod::Hash(ref value) => value.len(),
+            Pod::Bytes(ref value) => value.len(),
+            _ => 0,
         }
     }
-}
 
-impl IndexMut<usize> for Pod {
-    /// Easily access mutable element of Pod::Array by usize index
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+    /// Whether a `Pod` holds nothing: `Pod::Null`, and empty strings, arrays, hashes and bytes.
+    /// Any other scalar, such as `Pod::Integer(0)` or `Pod::Boolean(false)`, is not empty.
+    pub fn is_empty(&self) -> bool {
         match *self {
-            Pod::Array(ref mut vec) => {
-                let in_bounds = index < vec.len();
+            Pod::Null => true,
+            Pod::String(ref value) => value.is_empty(),
+            Pod::Integer(_) | Pod::Float(_) | Pod::Boolean(_) => false,
+            _ => self.len() == 0,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(*self, Pod::Null)
+    }
+
+    pub fn is_string(&self) -> bool {
+        matches!(*self, Pod::String(_))
+    }
+
+    pub fn is_integer(&self) -> bool {
+        matches!(*self, Pod::Integer(_))
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(*self, Pod::Float(_))
+    }
+
+    pub fn is_bool(&self) -> bool {
+        matches!(*self, Pod::Boolean(_))
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(*self, Pod::Array(_))
+    }
+
+    pub fn is_hash(&self) -> bool {
+        matches!(*self, Pod::Hash(_))
+    }
+
+    pub fn is_bytes(&self) -> bool {
+        matches!(*self, Pod::Bytes(_))
+    }
+
+    pub fn as_string(&self) -> Result<String, Error> {
+        match *self {
+            Pod::String(ref value) => Ok(value.clone()),
+            _ => Err(self.type_mismatch("String")),
+        }
+    }
+
+    pub fn as_i64(&self) -> Result<i64, Error> {
+        match *self {
+            Pod::Integer(ref value) => Ok(*value),
+            _ => Err(self.type_mismatch("Integer")),
+        }
+    }
+
+    pub fn as_f64(&self) -> Result<f64, Error> {
+        match *self {
+            Pod::Float(ref value) => Ok(*value),
+            _ => Err(self.type_mismatch("Float")),
+        }
+    }
+
+    /// Returns the value of a `Pod::Integer` or a `Pod::Float` as `f64`, and `None` for any other
+    /// type. Handy for numbers which some formats parse as either.
+    pub fn as_number(&self) -> Option<f64> {
+        match *self {
+            Pod::Integer(value) => Some(value as f64),
+            Pod::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Result<bool, Error> {
+        match *self {
+            Pod::Boolean(ref value) => Ok(*value),
+            _ => Err(self.type_mismatch("Boolean")),
+        }
+    }
+
+    pub fn as_vec(&self) -> Result<Vec<Pod>, Error> {
+        match *self {
+            Pod::Array(ref value) => Ok(value.clone()),
+            _ => Err(self.type_mismatch("Array")),
+        }
+    }
+
+    pub fn as_hashmap(&self) -> Result<HashMap<String, Pod>, Error> {
+        match *self {
+            Pod::Hash(ref value) => Ok(value.clone()),
+            _ => Err(self.type_mismatch("Hash")),
+        }
+    }
+
+    /// Borrows the elements of a Pod::Array, unlike [`as_vec`](Pod::as_vec) which clones them.
+    /// Returns `None` for other types.
+    pub fn as_vec_ref(&self) -> Option<&Vec<Pod>> {
+        match *self {
+            Pod::Array(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Borrows the entries of a Pod::Hash, unlike [`as_hashmap`](Pod::as_hashmap) which clones
+    /// them. Returns `None` for other types.
+    pub fn as_hashmap_ref(&self) -> Option<&HashMap<String, Pod>> {
+        match *self {
+            Pod::Hash(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Renders any `Pod` as a string. Scalars are stringified as is, `Pod::Null` becomes an empty
+    /// string, `Pod::Bytes` is base64 encoded and collections are rendered as `[a, b]` and
+    /// `{key: value}` with sorted keys.
+    pub fn as_string_lossy(&self) -> String {
+        match *self {
+            Pod::Null => String::new(),
+            Pod::String(ref value) => value.clone(),
+            Pod::Integer(ref value) => value.to_string(),
+            Pod::Float(ref value) => value.to_string(),
+            Pod::Boolean(ref value) => value.to_string(),
+            Pod::Array(ref value) => {
+                let items = value.iter().map(Pod::as_string_lossy).collect::<Vec<_>>();
+                format!("[{}]", items.join(", "))
+            }
+            Pod::Hash(ref value) => {
+                let mut keys = value.keys().collect::<Vec<_>>();
+                keys.sort();
+                let items = keys
+                    .into_iter()
+                    .map(|key| format!("{key}: {}", value[key].as_string_lossy()))
+                    .collect::<Vec<_>>();
+                format!("{{{}}}", items.join(", "))
+            }
+            Pod::Bytes(ref value) => base64_encode(value),
+        }
+    }
+
+    /// Stringifies every value of a `Pod::Hash` with [`as_string_lossy`](Pod::as_string_lossy).
+    /// Nested arrays and hashes are skipped unless `render_collections` is set. Returns an empty map
+    /// for other types.
+    pub fn to_string_map(&self, render_collections: bool) -> HashMap<String, String> {
+        match *self {
+            Pod::Hash(ref hash) => hash
+                .iter()
+                .filter(|(_, value)| render_collections || !(value.is_array() || value.is_hash()))
+                .map(|(key, value)| (key.clone(), value.as_string_lossy()))
+                .collect(),
+            _ => HashMap::new(),
+        }
+    }
+
+    /// Moves the inner value out of a `Pod::String` without cloning it.
+    pub fn into_string(self) -> Result<String, Error> {
+        match self {
+            Pod::String(value) => Ok(value),
+            _ => Err(self.type_mismatch("String")),
+        }
+    }
+
+    /// Moves the inner value out of a `Pod::Array` without cloning it.
+    pub fn into_vec(self) -> Result<Vec<Pod>, Error> {
+        match self {
+            Pod::Array(value) => Ok(value),
+            _ => Err(self.type_mismatch("Array")),
+        }
+    }
+
+    /// Moves the inner value out of a `Pod::Hash` without cloning it.
+    pub fn into_hashmap(self) -> Result<HashMap<String, Pod>, Error> {
+        match self {
+            Pod::Hash(value) => Ok(value),
+            _ => Err(self.type_mismatch("Hash")),
+        }
+    }
+
+    /// Parses a `Pod::String` holding a date into seconds since the Unix epoch. Accepts RFC 3339
+    /// datetimes, as well as datetimes without offset and plain `YYYY-MM-DD` dates, which are both
+    /// taken as UTC.
+    #[cfg(feature = "chrono")]
+    pub fn as_epoch_seconds(&self) -> Result<i64, Error> {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+        let value = match *self {
+            Pod::String(ref value) => value.trim(),
+            _ => return Err(self.type_mismatch("String")),
+        };
+        if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+            return Ok(datetime.timestamp());
+        }
+        for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+            if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+                return Ok(datetime.and_utc().timestamp());
+            }
+        }
+        match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            Ok(date) => Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp()),
+            Err(_) => Err(Error::deserialize_error(format!(
+                "invalid datetime: {value}"
+            ))),
+        }
+    }
+
+    pub fn as_bytes(&self) -> Result<Vec<u8>, Error> {
+        match *self {
+            Pod::Bytes(ref value) => Ok(value.clone()),
+            _ => Err(self.type_mismatch("Bytes")),
+        }
+    }
+}
+
+impl Into<String> for Pod {
+    fn into(self) -> String {
+        self.into_string().unwrap()
+    }
+}
+
+impl Into<i64> for Pod {
+    fn into(self) -> i64 {
+        self.as_i64().unwrap()
+    }
+}
+
+impl Into<f64> for Pod {
+    fn into(self) -> f64 {
+        self.as_f64().unwrap()
+    }
+}
+
+impl Into<bool> for Pod {
+    fn into(self) -> bool {
+        self.as_bool().unwrap()
+    }
+}
+
+impl Into<Vec<Pod>> for Pod {
+    fn into(self) -> Vec<Pod> {
+        self.into_vec().unwrap()
+    }
+}
+
+impl Into<HashMap<String, Pod>> for Pod {
+    fn into(self) -> HashMap<String, Pod> {
+        self.into_hashmap().unwrap()
+    }
+}
+
+impl Into<Vec<u8>> for Pod {
+    fn into(self) -> Vec<u8> {
+        self.as_bytes().unwrap()
+    }
+}
+
+impl From<i64> for Pod {
+    fn from(val: i64) -> Self {
+        Pod::Integer(val)
+    }
+}
+
+impl From<f64> for Pod {
+    fn from(val: f64) -> Self {
+        Pod::Float(val)
+    }
+}
+
+impl From<i32> for Pod {
+    fn from(val: i32) -> Self {
+        Pod::Integer(val.into())
+    }
+}
+
+impl From<u32> for Pod {
+    fn from(val: u32) -> Self {
+        Pod::Integer(val.into())
+    }
+}
+
+impl From<u64> for Pod {
+    /// Converts into a Pod::Integer, or a Pod::String of the digits for values too large for it,
+    /// like the engines do
+    fn from(val: u64) -> Self {
+        i64::try_from(val).map_or_else(|_| Pod::String(val.to_string()), Pod::Integer)
+    }
+}
+
+impl From<f32> for Pod {
+    fn from(val: f32) -> Self {
+        Pod::Float(val.into())
+    }
+}
+
+impl From<&str> for Pod {
+    fn from(val: &str) -> Self {
+        Pod::String(val.to_string())
+    }
+}
+
+impl From<String> for Pod {
+    fn from(val: String) -> Self {
+        Pod::String(val)
+    }
+}
+
+impl From<bool> for Pod {
+    fn from(val: bool) -> Self {
+        Pod::Boolean(val)
+    }
+}
+
+impl From<Vec<Pod>> for Pod {
+    fn from(val: Vec<Pod>) -> Self {
+        Pod::Array(val)
+    }
+}
+
+impl From<HashMap<String, Pod>> for Pod {
+    fn from(val: HashMap<String, Pod>) -> Self {
+        Pod::Hash(val)
+    }
+}
+
+impl From<Vec<u8>> for Pod {
+    fn from(val: Vec<u8>) -> Self {
+        Pod::Bytes(val)
+    }
+}
+
+impl FromIterator<Pod> for Pod {
+    /// Collects the items into a Pod::Array
+    fn from_iter<I: IntoIterator<Item = Pod>>(iter: I) -> Self {
+        Pod::Array(iter.into_iter().collect())
+    }
+}
+
+impl FromIterator<(String, Pod)> for Pod {
+    /// Collects the key value pairs into a Pod::Hash
+    fn from_iter<I: IntoIterator<Item = (String, Pod)>>(iter: I) -> Self {
+        Pod::Hash(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a Pod {
+    type Item = &'a Pod;
+    type IntoIter = slice::Iter<'a, Pod>;
+
+    /// Iterates over the elements of a Pod::Array, see [`Pod::iter`]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Extend<Pod> for Pod {
+    /// Appends the items to Pod::Array. Pod::Null is replaced by an empty Pod::Array first
+    ///
+    /// # Panics
+    ///
+    /// Panics for any other type, which is left untouched.
+    fn extend<I: IntoIterator<Item = Pod>>(&mut self, iter: I) {
+        if self.is_null() {
+            *self = Pod::new_array();
+        }
+        match *self {
+            Pod::Array(ref mut vec) => vec.extend(iter),
+            _ => panic!("cannot extend {} with array elements", self.type_name()),
+        }
+    }
+}
+
+impl Extend<(String, Pod)> for Pod {
+    /// Inserts the key value pairs into Pod::Hash. Pod::Null is replaced by an empty Pod::Hash
+    /// first
+    ///
+    /// # Panics
+    ///
+    /// Panics for any other type, which is left untouched.
+    fn extend<I: IntoIterator<Item = (String, Pod)>>(&mut self, iter: I) {
+        if self.is_null() {
+            *self = Pod::new_hash();
+        }
+        match *self {
+            Pod::Hash(ref mut hash) => hash.extend(iter),
+            _ => panic!("cannot extend {} with key value pairs", self.type_name()),
+        }
+    }
+}
+
+impl fmt::Display for Pod {
+    /// Prints scalars plainly, e.g. `hello` or `42`, and collections in a compact JSON-like form
+    /// with sorted keys, e.g. `{"tags": ["a", "b"], "weight": 1}`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Pod::Null => f.write_str("null"),
+            Pod::String(ref value) => f.write_str(value),
+            Pod::Integer(value) => write!(f, "{value}"),
+            Pod::Float(value) => write!(f, "{value}"),
+            Pod::Boolean(value) => write!(f, "{value}"),
+            Pod::Array(ref value) => {
+                f.write_str("[")?;
+                for (i, item) in value.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    item.fmt_nested(f)?;
+                }
+                f.write_str("]")
+            }
+            Pod::Hash(_) => {
+                f.write_str("{")?;
+                for (i, (key, item)) in self.sorted_entries().into_iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key:?}: ")?;
+                    item.fmt_nested(f)?;
+                }
+                f.write_str("}")
+            }
+            Pod::Bytes(ref value) => f.write_str(&base64_encode(value)),
+        }
+    }
+}
+
+impl Index<usize> for Pod {
+    type Output = Pod;
+
+    /// Easily access element of Pod::Array by usize index
+    fn index(&self, index: usize) -> &Self::Output {
+        match *self {
+            Pod::Array(ref vec) => vec.get(index).unwrap_or(&NULL),
+            _ => &NULL,
+        }
+    }
+}
+
+impl IndexMut<usize> for Pod {
+    /// Easily access mutable element of Pod::Array by usize index
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match *self {
+            Pod::Array(ref mut vec) => {
+                let in_bounds = index < vec.len();
                 if in_bounds {
                     &mut vec[index]
                 } else {
@@ -317,26 +1390,106 @@ impl Into<json::Value> for Pod {
             }
             Pod::Hash(val) => {
                 use json::Map;
+                let mut entries = val.into_iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
                 let mut hash = Map::new();
-                for (key, value) in val.into_iter() {
+                for (key, value) in entries.into_iter() {
                     hash.insert(key, value.into());
                 }
                 Object(hash)
             }
+            Pod::Bytes(val) => json!(base64_encode(&val)),
         }
     }
 }
 
-#[test]
-fn test_partial_compare_null() -> std::result::Result<(), Error> {
-    assert!(Pod::Null == Pod::Null);
-    Ok(())
+/// Reads `value` as a boolean, an integer or a float, if it unambiguously is one.
+fn coerce_scalar(value: &str) -> Option<Pod> {
+    match value {
+        "true" => return Some(Pod::Boolean(true)),
+        "false" => return Some(Pod::Boolean(false)),
+        _ => {}
+    }
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+    if !digits.starts_with(|c: char| c.is_ascii_digit()) || leading_zero {
+        return None;
+    }
+    if let Ok(int) = value.parse::<i64>() {
+        Some(Pod::Integer(int))
+    } else {
+        value
+            .parse::<f64>()
+            .ok()
+            .filter(|float| float.is_finite())
+            .map(Pod::Float)
+    }
 }
 
-#[test]
-fn test_partial_compare_boolean() -> std::result::Result<(), Error> {
-    assert!(Pod::Boolean(true) == Pod::Boolean(true));
-    assert!(Pod::Boolean(true) != Pod::Boolean(false));
+fn write_canonical_json(value: &json::Value, out: &mut String) -> IResult<()> {
+    use json::Value::*;
+    match value {
+        Array(vec) => {
+            out.push('[');
+            for (i, item) in vec.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out)?;
+            }
+            out.push(']');
+        }
+        Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&json::to_string(key)?);
+                out.push(':');
+                write_canonical_json(item, out)?;
+            }
+            out.push('}');
+        }
+        _ => out.push_str(&json::to_string(value)?),
+    }
+    Ok(())
+}
+
+/// Encodes `bytes` with the standard, padded base64 alphabet.
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[test]
+fn test_partial_compare_null() -> std::result::Result<(), Error> {
+    assert!(Pod::Null == Pod::Null);
+    Ok(())
+}
+
+#[test]
+fn test_partial_compare_boolean() -> std::result::Result<(), Error> {
+    assert!(Pod::Boolean(true) == Pod::Boolean(true));
+    assert!(Pod::Boolean(true) != Pod::Boolean(false));
     Ok(())
 }
 
@@ -402,6 +1555,18 @@ fn test_partial_compare_float() -> std::result::Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_partial_compare_nan() -> std::result::Result<(), Error> {
+    assert!(Pod::Float(f64::NAN) == Pod::Float(f64::NAN));
+    assert!(Pod::Float(f64::NAN) != Pod::Float(1.0));
+    assert!(Pod::Float(1.0) != Pod::Float(f64::NAN));
+    let a = Pod::Array(vec![Pod::Float(f64::NAN)]);
+    assert!(a == a.clone());
+    assert!(Pod::Float(0.0) == Pod::Float(-0.0));
+    assert!(Pod::Float(1.0) != Pod::Integer(1));
+    Ok(())
+}
+
 #[test]
 fn test_len_and_is_empty_of_pod() -> std::result::Result<(), Error> {
     let mut a = Pod::new_array();
@@ -411,7 +1576,49 @@ fn test_len_and_is_empty_of_pod() -> std::result::Result<(), Error> {
     b["hello"] = Pod::String("world".into());
     b["boolean"] = Pod::Boolean(true);
     assert!(b.len() == 2);
-    assert!(Pod::String("hello".into()).is_empty());
+    assert!(!a.is_empty());
+    assert!(Pod::new_hash().is_empty());
+    assert!(Pod::new_array().is_empty());
+    assert!(Pod::Bytes(vec![]).is_empty());
+    assert!(Pod::Null.is_empty());
+    assert!(Pod::String("".into()).is_empty());
+    assert!(!Pod::String("x".into()).is_empty());
+    assert!(!Pod::Integer(0).is_empty());
+    assert!(!Pod::Float(0.0).is_empty());
+    assert!(!Pod::Boolean(false).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_type_predicates() -> std::result::Result<(), Error> {
+    assert!(Pod::Null.is_null());
+    assert!(Pod::String("hello".into()).is_string());
+    assert!(Pod::Integer(1).is_integer());
+    assert!(Pod::Float(1.0).is_float());
+    assert!(Pod::Boolean(true).is_bool());
+    assert!(Pod::new_array().is_array());
+    assert!(Pod::new_hash().is_hash());
+    assert!(!Pod::Integer(1).is_float());
+    assert!(!Pod::String("hello".into()).is_null());
+    assert!(!Pod::new_hash().is_array());
+    Ok(())
+}
+
+#[test]
+fn test_type_name() -> std::result::Result<(), Error> {
+    assert_eq!(Pod::Null.type_name(), "null");
+    assert_eq!(Pod::String("hello".into()).type_name(), "string");
+    assert_eq!(Pod::Integer(1).type_name(), "integer");
+    assert_eq!(Pod::Float(1.0).type_name(), "float");
+    assert_eq!(Pod::Boolean(true).type_name(), "boolean");
+    assert_eq!(Pod::new_array().type_name(), "array");
+    assert_eq!(Pod::new_hash().type_name(), "hash");
+    assert_eq!(Pod::Bytes(vec![]).type_name(), "bytes");
+    let err = Pod::String("hello".into()).as_i64().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Type error, expected: Integer, found: string"
+    );
     Ok(())
 }
 
@@ -472,6 +1679,887 @@ fn test_pod_from_into() -> std::result::Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn test_pod_bytes() -> std::result::Result<(), Error> {
+    let pod = Pod::from(b"hello".to_vec());
+    assert!(pod.is_bytes());
+    assert!(pod.len() == 5);
+    assert!(pod.as_bytes()? == b"hello".to_vec());
+    assert!(Pod::Null.as_bytes() == Err(Error::type_mismatch("Bytes", "null")));
+    assert_eq!(pod.to_json(), json::Value::String("aGVsbG8=".to_string()));
+    assert_eq!(pod.deserialize::<Vec<u8>>()?, b"hello".to_vec());
+    assert_eq!(pod.deserialize_lenient::<Vec<u8>>()?, b"hello".to_vec());
+    assert_eq!(pod.deserialize::<String>()?, "hello");
+    assert_eq!(pod.deserialize_lenient::<String>()?, "hello");
+    assert!(Pod::Bytes(vec![0xff]).deserialize::<String>().is_err());
+    assert!(Pod::Bytes(vec![0xff])
+        .deserialize_lenient::<String>()
+        .is_err());
+    assert_eq!(base64_encode(b""), "");
+    assert_eq!(base64_encode(b"f"), "Zg==");
+    assert_eq!(base64_encode(b"fo"), "Zm8=");
+    assert_eq!(base64_encode(b"foo"), "Zm9v");
+    Ok(())
+}
+
+#[test]
+fn test_pod_to_string_map() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["title"] = Pod::String("hello".to_string());
+    pod["weight"] = Pod::Integer(2);
+    pod["ratio"] = Pod::Float(0.5);
+    pod["draft"] = Pod::Boolean(false);
+    pod["empty"] = Pod::Null;
+    pod["tags"] = Pod::Array(vec![Pod::String("a".into()), Pod::Integer(1)]);
+    pod["nested"]["b"] = Pod::Integer(2);
+    pod["nested"]["a"] = Pod::Integer(1);
+
+    let map = pod.to_string_map(false);
+    assert_eq!(map.len(), 5);
+    assert_eq!(map["title"], "hello");
+    assert_eq!(map["weight"], "2");
+    assert_eq!(map["ratio"], "0.5");
+    assert_eq!(map["draft"], "false");
+    assert_eq!(map["empty"], "");
+
+    let map = pod.to_string_map(true);
+    assert_eq!(map.len(), 7);
+    assert_eq!(map["tags"], "[a, 1]");
+    assert_eq!(map["nested"], "{a: 1, b: 2}");
+
+    assert!(Pod::Integer(1).to_string_map(true).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_pod_to_and_from_json() -> std::result::Result<(), Error> {
+    let value = json::json!({
+        "title": "Home",
+        "weight": 3,
+        "ratio": 0.5,
+        "draft": false,
+        "tags": ["a", null],
+        "nested": {"empty": {}}
+    });
+    let pod = Pod::from_json(&value);
+    assert_eq!(pod["title"], Pod::String("Home".into()));
+    assert_eq!(pod["weight"], Pod::Integer(3));
+    assert_eq!(pod["tags"][1], Pod::Null);
+    assert_eq!(pod.to_json(), value);
+    assert_eq!(Pod::from_json(&pod.to_json()), pod);
+    assert_eq!(Pod::Bytes(b"hi".to_vec()).to_json(), json::json!("aGk="));
+    Ok(())
+}
+
+#[test]
+fn test_pod_to_canonical_json() -> std::result::Result<(), Error> {
+    let mut a = Pod::new_hash();
+    a["b"] = Pod::Integer(1);
+    a["a"]["y"] = Pod::Array(vec![Pod::String("x y".into()), Pod::Null]);
+    a["a"]["x"] = Pod::Float(1.5);
+    let mut b = Pod::new_hash();
+    b["a"]["x"] = Pod::Float(1.5);
+    b["a"]["y"] = Pod::Array(vec![Pod::String("x y".into()), Pod::Null]);
+    b["b"] = Pod::Integer(1);
+    assert_eq!(a.to_canonical_json()?, b.to_canonical_json()?);
+    assert_eq!(
+        a.to_canonical_json()?,
+        r#"{"a":{"x":1.5,"y":["x y",null]},"b":1}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_pod_eq_unordered() -> std::result::Result<(), Error> {
+    let mut a = Pod::new_hash();
+    a["tags"] = Pod::Array(vec![
+        Pod::String("rust".into()),
+        Pod::Array(vec![Pod::Integer(1), Pod::Integer(2), Pod::Integer(2)]),
+        Pod::String("yaml".into()),
+    ]);
+    let mut b = Pod::new_hash();
+    b["tags"] = Pod::Array(vec![
+        Pod::Array(vec![Pod::Integer(2), Pod::Integer(1), Pod::Integer(2)]),
+        Pod::String("yaml".into()),
+        Pod::String("rust".into()),
+    ]);
+    assert_ne!(a, b);
+    assert!(a.eq_unordered(&b));
+    assert!(b.eq_unordered(&a));
+
+    b["tags"][0] = Pod::Array(vec![Pod::Integer(1), Pod::Integer(1), Pod::Integer(2)]);
+    assert!(!a.eq_unordered(&b), "should count duplicates");
+    b["tags"][0] = Pod::Array(vec![Pod::Integer(1), Pod::Integer(2)]);
+    assert!(!a.eq_unordered(&b));
+    b["extra"] = Pod::Null;
+    assert!(!a.eq_unordered(&b));
+    Ok(())
+}
+
+#[test]
+fn test_pod_display() -> std::result::Result<(), Error> {
+    assert_eq!(Pod::Null.to_string(), "null");
+    assert_eq!(Pod::String("hello".into()).to_string(), "hello");
+    assert_eq!(Pod::Integer(42).to_string(), "42");
+    assert_eq!(Pod::Float(1.5).to_string(), "1.5");
+    assert_eq!(Pod::Boolean(true).to_string(), "true");
+    assert_eq!(Pod::Bytes(b"hi".to_vec()).to_string(), "aGk=");
+    let mut pod = Pod::new_hash();
+    pod["weight"] = Pod::Integer(1);
+    pod["tags"] = Pod::Array(vec![Pod::String("a \"b\"".into()), Pod::Null]);
+    pod["nested"]["bin"] = Pod::Bytes(b"hi".to_vec());
+    pod["nested"]["empty"] = Pod::new_array();
+    assert_eq!(
+        pod.to_string(),
+        r#"{"nested": {"bin": "aGk=", "empty": []}, "tags": ["a \"b\"", null], "weight": 1}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_pod_retain() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["title"] = Pod::String("Home".into());
+    pod["_draft"] = Pod::Boolean(true);
+    pod["_internal_id"] = Pod::Integer(7);
+    pod.retain(|key, _| !key.starts_with('_'));
+    assert_eq!(pod.len(), 1);
+    assert_eq!(pod["title"], Pod::String("Home".into()));
+
+    let mut pod: Pod = (1..=6).map(Pod::Integer).collect();
+    pod.retain_array(|item| item.as_i64().is_ok_and(|i| i % 2 == 0));
+    assert_eq!(pod, (1..=3).map(|i| Pod::Integer(i * 2)).collect());
+
+    let mut pod = Pod::Integer(1);
+    pod.retain(|_, _| false);
+    pod.retain_array(|_| false);
+    assert_eq!(pod, Pod::Integer(1));
+    Ok(())
+}
+
+#[test]
+fn test_pod_walk() -> std::result::Result<(), Error> {
+    use PathSegment::{Index, Key};
+    let mut pod = Pod::new_hash();
+    pod["b"] = Pod::Array(vec![Pod::Integer(1), Pod::Array(vec![Pod::Boolean(true)])]);
+    pod["a"]["y"] = Pod::Null;
+    pod["a"]["x"] = Pod::String("x".into());
+    let mut visited = vec![];
+    pod.walk(|path, node| visited.push((path.to_vec(), node.type_name())));
+    assert_eq!(
+        visited,
+        vec![
+            (vec![], "hash"),
+            (vec![Key("a".into())], "hash"),
+            (vec![Key("a".into()), Key("x".into())], "string"),
+            (vec![Key("a".into()), Key("y".into())], "null"),
+            (vec![Key("b".into())], "array"),
+            (vec![Key("b".into()), Index(0)], "integer"),
+            (vec![Key("b".into()), Index(1)], "array"),
+            (vec![Key("b".into()), Index(1), Index(0)], "boolean"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+#[allow(clippy::approx_constant)]
+fn test_pod_coerce_scalars() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["bool"] = Pod::String("true".into());
+    pod["int"] = Pod::String("42".into());
+    pod["negative"] = Pod::String("-7".into());
+    pod["float"] = Pod::String("3.14".into());
+    pod["exp"] = Pod::String("1e3".into());
+    pod["nested"] = Pod::Array(vec![Pod::String("false".into()), Pod::String("0.5".into())]);
+    for ambiguous in [
+        "hello", "042", "+1", ".5", "inf", "NaN", "True", "", "1.2.3",
+    ] {
+        pod[ambiguous] = Pod::String(ambiguous.into());
+    }
+    pod.coerce_scalars();
+    assert_eq!(pod["bool"], Pod::Boolean(true));
+    assert_eq!(pod["int"], Pod::Integer(42));
+    assert_eq!(pod["negative"], Pod::Integer(-7));
+    assert_eq!(pod["float"], Pod::Float(3.14));
+    assert_eq!(pod["exp"], Pod::Float(1000.0));
+    assert_eq!(
+        pod["nested"],
+        Pod::Array(vec![Pod::Boolean(false), Pod::Float(0.5)])
+    );
+    for ambiguous in [
+        "hello", "042", "+1", ".5", "inf", "NaN", "True", "", "1.2.3",
+    ] {
+        assert_eq!(pod[ambiguous], Pod::String(ambiguous.into()));
+    }
+    Ok(())
+}
+
+#[test]
+fn test_pod_get_ci() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["Title"] = Pod::String("Home".into());
+    pod["DRAFT"] = Pod::Boolean(true);
+    pod["Tags"] = Pod::Integer(1);
+    pod["tags"] = Pod::Integer(2);
+    pod["TAGS"] = Pod::Integer(3);
+    assert_eq!(pod.get_ci("title"), Some(&Pod::String("Home".into())));
+    assert_eq!(pod.get_ci("Draft"), Some(&Pod::Boolean(true)));
+    assert_eq!(pod.get_ci("tags"), Some(&Pod::Integer(2)));
+    assert_eq!(pod.get_ci("tAgS"), Some(&Pod::Integer(3)));
+    assert_eq!(pod.get_ci("author"), None);
+    assert_eq!(Pod::new_array().get_ci("title"), None);
+    Ok(())
+}
+
+#[test]
+fn test_pod_get_path() -> std::result::Result<(), Error> {
+    use PathSegment::{Index, Key};
+    let mut pod = Pod::new_hash();
+    pod["authors"] = Pod::Array(vec![Pod::new_hash()]);
+    pod["authors"][0]["name"] = Pod::String("me".into());
+    pod["authors"][0]["links"] = Pod::Array(vec![Pod::String("a".into()), Pod::String("b".into())]);
+    assert_eq!(pod.get_path(&[]), Some(&pod));
+    assert_eq!(
+        pod.get_path(&[Key("authors".into()), Index(0), Key("name".into())]),
+        Some(&Pod::String("me".into()))
+    );
+    assert_eq!(
+        pod.get_path(&[
+            Key("authors".into()),
+            Index(0),
+            Key("links".into()),
+            Index(1)
+        ]),
+        Some(&Pod::String("b".into()))
+    );
+    assert_eq!(pod.get_path(&[Key("authors".into()), Index(1)]), None);
+    assert_eq!(
+        pod.get_path(&[Key("authors".into()), Key("0".into())]),
+        None
+    );
+    assert_eq!(pod.get_path(&[Index(0)]), None);
+    let mut paths = vec![];
+    pod.walk(|path, _| paths.push(path.to_vec()));
+    assert!(paths.iter().all(|path| pod.get_path(path).is_some()));
+    Ok(())
+}
+
+#[test]
+fn test_pod_as_number() -> std::result::Result<(), Error> {
+    assert_eq!(Pod::Integer(3).as_number(), Some(3.0));
+    assert_eq!(Pod::Float(2.5).as_number(), Some(2.5));
+    assert_eq!(Pod::String("3".into()).as_number(), None);
+    assert_eq!(Pod::Null.as_number(), None);
+    Ok(())
+}
+
+#[test]
+fn test_pod_sorted_entries() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["b"] = Pod::Integer(2);
+    pod["c"] = Pod::Integer(3);
+    pod["a"] = Pod::Integer(1);
+    let keys = pod
+        .sorted_entries()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect::<Vec<_>>();
+    assert_eq!(keys, vec!["a", "b", "c"]);
+    assert_eq!(pod.sorted_entries()[0].1, &Pod::Integer(1));
+    assert!(Pod::new_array().sorted_entries().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_pod_to_vec_of() -> std::result::Result<(), Error> {
+    let tags = Pod::Array(vec![Pod::String("rust".into()), Pod::String("yaml".into())]);
+    assert_eq!(tags.to_vec_of::<String>()?, vec!["rust", "yaml"]);
+    let weights = Pod::Array(vec![Pod::Integer(1), Pod::Integer(-2)]);
+    assert_eq!(weights.to_vec_of::<i64>()?, vec![1, -2]);
+    assert!(Pod::new_array().to_vec_of::<i64>()?.is_empty());
+
+    match tags.to_vec_of::<i64>() {
+        Err(Error::DeserializeError(msg)) => assert!(msg.starts_with("at 0: "), "{}", msg),
+        result => panic!("should fail on the first element, got {:?}", result),
+    }
+    assert_eq!(
+        Pod::String("rust".into()).to_vec_of::<String>(),
+        Err(Error::type_mismatch("Array", "string"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_pod_try_get_as() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["title"] = Pod::String("Home".into());
+    pod["weight"] = Pod::Integer(3);
+    pod["tags"] = Pod::Array(vec![Pod::String("rust".into())]);
+    assert_eq!(pod.try_get_as::<String>("title")?, "Home");
+    assert_eq!(pod.try_get_as::<i64>("weight")?, 3);
+    assert_eq!(pod.try_get_as::<Vec<String>>("tags")?, vec!["rust"]);
+    assert_eq!(pod.try_get_as::<Option<i64>>("weight")?, Some(3));
+
+    assert_eq!(
+        pod.try_get_as::<String>("author"),
+        Err(Error::ValueMissingError)
+    );
+    assert!(matches!(
+        pod.try_get_as::<i64>("title"),
+        Err(Error::DeserializeError(_))
+    ));
+    assert_eq!(
+        Pod::new_array().try_get_as::<i64>("weight"),
+        Err(Error::type_mismatch("Hash", "array"))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_pod_from_primitives() -> std::result::Result<(), Error> {
+    assert_eq!(Pod::from("hello"), Pod::String("hello".to_string()));
+    assert_eq!(Pod::from(-3i32), Pod::Integer(-3));
+    assert_eq!(Pod::from(3u32), Pod::Integer(3));
+    assert_eq!(Pod::from(3u64), Pod::Integer(3));
+    assert_eq!(
+        Pod::from(u64::MAX),
+        Pod::String("18446744073709551615".to_string())
+    );
+    assert_eq!(Pod::from(1.5f32), Pod::Float(1.5));
+
+    let mut pod = Pod::new_hash();
+    pod.insert("title".to_string(), "Home")?;
+    assert_eq!(pod["title"], Pod::String("Home".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_pod_as_ref() -> std::result::Result<(), Error> {
+    let mut hash = Pod::new_hash();
+    hash["tags"] = Pod::Array(vec![Pod::from("rust")]);
+    let map = hash.as_hashmap_ref().unwrap();
+    assert_eq!(map.len(), 1);
+    match hash {
+        Pod::Hash(ref value) => assert!(std::ptr::eq(map, value), "should borrow the hash"),
+        _ => unreachable!(),
+    }
+
+    let array = &hash["tags"];
+    let vec = array.as_vec_ref().unwrap();
+    assert_eq!(vec, &vec![Pod::from("rust")]);
+    match *array {
+        Pod::Array(ref value) => assert!(std::ptr::eq(vec, value), "should borrow the array"),
+        _ => unreachable!(),
+    }
+
+    assert!(hash.as_vec_ref().is_none());
+    assert!(array.as_hashmap_ref().is_none());
+    assert!(Pod::Null.as_vec_ref().is_none());
+    assert!(Pod::from("rust").as_hashmap_ref().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_pod_validate() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["title"] = Pod::from("Home");
+    pod["weight"] = Pod::from(3);
+    pod["draft"] = Pod::Null;
+    let spec = [("title", PodType::String), ("weight", PodType::Integer)];
+    assert_eq!(pod.validate(&spec), Ok(()));
+    assert_eq!(pod.validate(&[]), Ok(()));
+
+    let spec = [
+        ("title", PodType::String),
+        ("weight", PodType::Float),
+        ("draft", PodType::Boolean),
+        ("tags", PodType::Array),
+        ("author", PodType::String),
+    ];
+    assert_eq!(
+        pod.validate(&spec),
+        Err(vec![
+            ValidationError::Mismatch {
+                key: "weight".into(),
+                expected: PodType::Float,
+                found: PodType::Integer,
+            },
+            ValidationError::Mismatch {
+                key: "draft".into(),
+                expected: PodType::Boolean,
+                found: PodType::Null,
+            },
+            ValidationError::Missing("tags".into()),
+            ValidationError::Missing("author".into()),
+        ])
+    );
+    assert_eq!(
+        Pod::new_array().validate(&[("title", PodType::String)]),
+        Err(vec![ValidationError::Missing("title".into())])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_pod_node_count_and_max_depth() -> std::result::Result<(), Error> {
+    assert_eq!(Pod::Integer(1).node_count(), 1);
+    assert_eq!(Pod::Integer(1).max_depth(), 0);
+    assert_eq!(Pod::new_hash().node_count(), 1);
+    assert_eq!(Pod::new_hash().max_depth(), 1);
+
+    let mut flat = Pod::new_hash();
+    flat["title"] = Pod::from("Home");
+    flat["weight"] = Pod::from(1);
+    assert_eq!(flat.node_count(), 3);
+    assert_eq!(flat.max_depth(), 1);
+
+    let mut nested = Pod::Integer(0);
+    for _ in 0..50 {
+        nested = Pod::Array(vec![nested, Pod::Null]);
+    }
+    assert_eq!(nested.node_count(), 101);
+    assert_eq!(nested.max_depth(), 50);
+
+    flat["nested"] = nested;
+    assert_eq!(flat.node_count(), 104);
+    assert_eq!(flat.max_depth(), 51);
+    Ok(())
+}
+
+#[test]
+fn test_pod_flatten_one_level() -> std::result::Result<(), Error> {
+    let mut pod = Pod::Array(vec![
+        Pod::Array(vec![Pod::from("a")]),
+        Pod::from("b"),
+        Pod::Array(vec![]),
+        Pod::Array(vec![Pod::from("c"), Pod::Array(vec![Pod::from("d")])]),
+        Pod::new_hash(),
+    ]);
+    pod.flatten_one_level();
+    assert_eq!(
+        pod,
+        Pod::Array(vec![
+            Pod::from("a"),
+            Pod::from("b"),
+            Pod::from("c"),
+            Pod::Array(vec![Pod::from("d")]),
+            Pod::new_hash(),
+        ])
+    );
+
+    let mut scalar = Pod::from("a");
+    scalar.flatten_one_level();
+    assert_eq!(scalar, Pod::from("a"));
+    Ok(())
+}
+
+#[test]
+fn test_pod_num_eq() -> std::result::Result<(), Error> {
+    assert!(Pod::Integer(1).num_eq(&Pod::Float(1.0)));
+    assert!(Pod::Float(1.0).num_eq(&Pod::Integer(1)));
+    assert!(!Pod::Integer(1).num_eq(&Pod::Float(1.5)));
+    assert!(!Pod::Float(1.5).num_eq(&Pod::Integer(1)));
+    assert_ne!(Pod::Integer(1), Pod::Float(1.0));
+
+    assert!(!Pod::Integer(i64::MAX).num_eq(&Pod::Float(i64::MAX as f64)));
+    assert!(Pod::Integer(i64::MIN).num_eq(&Pod::Float(i64::MIN as f64)));
+    assert!(!Pod::Integer(0).num_eq(&Pod::Float(f64::NAN)));
+    assert!(!Pod::Integer(1).num_eq(&Pod::String("1".into())));
+
+    let mut a = Pod::new_hash();
+    a["weight"] = Pod::Integer(2);
+    a["sizes"] = Pod::Array(vec![Pod::Float(1.0), Pod::Float(0.5)]);
+    let mut b = Pod::new_hash();
+    b["weight"] = Pod::Float(2.0);
+    b["sizes"] = Pod::Array(vec![Pod::Integer(1), Pod::Float(0.5)]);
+    assert!(a.num_eq(&b));
+    b["sizes"].push(Pod::Null)?;
+    assert!(!a.num_eq(&b));
+    Ok(())
+}
+
+#[test]
+fn test_pod_deserialize_with_seed() -> std::result::Result<(), Error> {
+    use serde::de::{Deserializer, SeqAccess, Visitor};
+
+    /// Deserializes a list of strings, counting them in the borrowed counter along the way.
+    struct Counting<'a>(&'a mut usize);
+
+    impl<'de, 'a> DeserializeSeed<'de> for Counting<'a> {
+        type Value = Vec<String>;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Vec<String>, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de, 'a> Visitor<'de> for Counting<'a> {
+        type Value = Vec<String>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a list of strings")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<String>, A::Error> {
+            let mut vec = Vec::new();
+            while let Some(item) = seq.next_element()? {
+                *self.0 += 1;
+                vec.push(item);
+            }
+            Ok(vec)
+        }
+    }
+
+    let pod = Pod::Array(vec![Pod::String("rust".into()), Pod::String("yaml".into())]);
+    let mut count = 0;
+    let tags = pod.deserialize_with_seed(Counting(&mut count))?;
+    assert_eq!(tags, vec!["rust", "yaml"]);
+    let tags = pod.deserialize_with_seed(Counting(&mut count))?;
+    assert_eq!(tags.len(), 2);
+    assert_eq!(count, 4);
+
+    let pod = Pod::Array(vec![Pod::String("rust".into()), Pod::Integer(1)]);
+    match pod.deserialize_with_seed(Counting(&mut count)) {
+        Err(Error::DeserializeError(msg)) => assert!(msg.starts_with("at 1: "), "{}", msg),
+        result => panic!("should fail on the integer, got {:?}", result),
+    }
+    assert!(Pod::Integer(1)
+        .deserialize_with_seed(Counting(&mut count))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_pod_partial_cmp() -> std::result::Result<(), Error> {
+    assert!(Pod::Integer(1) < Pod::Integer(2));
+    assert!(Pod::Float(1.5) > Pod::Float(0.5));
+    assert!(Pod::Integer(1) < Pod::Float(1.5));
+    assert!(Pod::Float(1.5) > Pod::Integer(1));
+    assert!(Pod::String("apple".into()) < Pod::String("banana".into()));
+    assert!(Pod::Boolean(false) < Pod::Boolean(true));
+    assert_eq!(
+        Pod::Integer(1).partial_cmp(&Pod::Integer(1)),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(
+        Pod::Float(f64::NAN).partial_cmp(&Pod::Float(f64::NAN)),
+        Some(Ordering::Equal)
+    );
+
+    assert_eq!(Pod::Integer(1).partial_cmp(&Pod::Float(1.0)), None);
+    assert_eq!(Pod::Float(f64::NAN).partial_cmp(&Pod::Float(1.0)), None);
+    assert_eq!(Pod::Integer(1).partial_cmp(&Pod::String("1".into())), None);
+    assert_eq!(Pod::Boolean(true).partial_cmp(&Pod::Integer(1)), None);
+    assert_eq!(Pod::Null.partial_cmp(&Pod::Integer(0)), None);
+    assert_eq!(Pod::new_array().partial_cmp(&Pod::new_hash()), None);
+    let one = Pod::Array(vec![Pod::Integer(1)]);
+    assert_eq!(one.partial_cmp(&Pod::Array(vec![Pod::Integer(2)])), None);
+    assert_eq!(one.partial_cmp(&one.clone()), Some(Ordering::Equal));
+    assert_eq!(Pod::Null.partial_cmp(&Pod::Null), Some(Ordering::Equal));
+
+    let mut posts: Vec<Pod> = (0..3).map(|_| Pod::new_hash()).collect();
+    posts[0]["weight"] = Pod::Integer(3);
+    posts[1]["weight"] = Pod::Float(0.5);
+    posts[2]["weight"] = Pod::Integer(2);
+    posts.sort_by(|a, b| a["weight"].partial_cmp(&b["weight"]).unwrap());
+    let weights: Vec<f64> = posts
+        .iter()
+        .filter_map(|post| post["weight"].as_number())
+        .collect();
+    assert_eq!(weights, vec![0.5, 2.0, 3.0]);
+    Ok(())
+}
+
+#[test]
+fn test_pod_fill_defaults() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["title"] = Pod::String("Home".into());
+    pod["draft"] = Pod::Null;
+    pod["tags"] = Pod::Array(vec![Pod::String("rust".into())]);
+    pod["seo"] = Pod::new_hash();
+    pod["seo"]["title"] = Pod::String("Home page".into());
+
+    let mut defaults = Pod::new_hash();
+    defaults["title"] = Pod::String("Untitled".into());
+    defaults["draft"] = Pod::Boolean(false);
+    defaults["tags"] = Pod::Array(vec![Pod::String("blog".into())]);
+    defaults["layout"] = Pod::String("post".into());
+    defaults["seo"] = Pod::new_hash();
+    defaults["seo"]["title"] = Pod::String("Site".into());
+    defaults["seo"]["robots"] = Pod::String("index".into());
+
+    pod.fill_defaults(&defaults);
+    assert_eq!(pod["title"], Pod::String("Home".into()));
+    assert!(pod["draft"].is_null());
+    assert_eq!(pod["tags"], Pod::Array(vec![Pod::String("rust".into())]));
+    assert_eq!(pod["layout"], Pod::String("post".into()));
+    assert_eq!(pod["seo"]["title"], Pod::String("Home page".into()));
+    assert_eq!(pod["seo"]["robots"], Pod::String("index".into()));
+    assert_eq!(pod.len(), 5);
+
+    let mut pod = Pod::Null;
+    pod.fill_defaults(&defaults);
+    assert!(pod.is_null());
+    let mut pod = Pod::new_hash();
+    pod["seo"] = Pod::String("none".into());
+    pod.fill_defaults(&defaults);
+    assert_eq!(pod["seo"], Pod::String("none".into()));
+    assert_eq!(pod.as_hashmap()?.len(), 5);
+    Ok(())
+}
+
+#[test]
+fn test_pod_rename_key() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["tag"] = Pod::Array(vec![Pod::String("rust".into())]);
+    pod["draft"] = Pod::Null;
+    assert!(pod.rename_key("tag", "tags"));
+    assert_eq!(pod["tags"], Pod::Array(vec![Pod::String("rust".into())]));
+    assert!(!pod.as_hashmap()?.contains_key("tag"));
+
+    assert!(!pod.rename_key("tag", "tags"));
+    assert_eq!(pod.len(), 2);
+    assert!(pod.rename_key("draft", "tags"));
+    assert_eq!(pod.len(), 1);
+    assert!(pod["tags"].is_null());
+    assert!(!Pod::new_array().rename_key("tag", "tags"));
+    Ok(())
+}
+
+#[test]
+fn test_pod_iter() -> std::result::Result<(), Error> {
+    let pod = Pod::Array(vec![Pod::Integer(1), Pod::Integer(2), Pod::Integer(3)]);
+    let mut sum = 0;
+    for item in &pod {
+        sum += item.as_i64()?;
+    }
+    assert_eq!(sum, 6);
+    assert_eq!(pod.iter().len(), 3);
+    assert_eq!(Pod::Integer(1).iter().count(), 0);
+    assert_eq!(pod.entries().count(), 0);
+
+    let mut hash = Pod::new_hash();
+    hash["a"] = Pod::Integer(1);
+    hash["b"] = Pod::Integer(2);
+    let mut keys = hash
+        .entries()
+        .map(|(key, _)| key.as_str())
+        .collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert_eq!(keys, vec!["a", "b"]);
+    assert_eq!((&hash).into_iter().count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_pod_into_inner() -> std::result::Result<(), Error> {
+    assert!(Pod::String("hello".into()).into_string()? == "hello");
+    assert!(Pod::Integer(1).into_string() == Err(Error::type_mismatch("String", "integer")));
+    let vec = vec![Pod::Boolean(true)];
+    assert!(Pod::Array(vec.clone()).into_vec()? == vec);
+    assert!(Pod::Null.into_vec() == Err(Error::type_mismatch("Array", "null")));
+    let mut hash = Pod::new_hash();
+    hash["hello"] = Pod::String("world".into());
+    assert!(hash.clone().into_hashmap()? == hash.as_hashmap()?);
+    assert!(Pod::new_array().into_hashmap() == Err(Error::type_mismatch("Hash", "array")));
+    Ok(())
+}
+
+#[test]
+fn test_pod_from_iter_and_extend() -> std::result::Result<(), Error> {
+    let pairs = vec![
+        ("hello".to_string(), Pod::String("world".into())),
+        ("answer".to_string(), Pod::Integer(42)),
+    ];
+    let mut hash: Pod = pairs.into_iter().collect();
+    assert!(hash.is_hash());
+    assert!(hash["answer"] == Pod::Integer(42));
+    hash.extend(vec![("answer".to_string(), Pod::Integer(0))]);
+    assert!(hash.len() == 2);
+    assert!(hash["answer"] == Pod::Integer(0));
+
+    let mut array: Pod = (1..3).map(Pod::Integer).collect();
+    assert!(array == Pod::Array(vec![Pod::Integer(1), Pod::Integer(2)]));
+    array.extend(vec![Pod::Null]);
+    assert!(array.len() == 3);
+
+    let mut null = Pod::Null;
+    null.extend(vec![Pod::Boolean(true)]);
+    assert!(null == Pod::Array(vec![Pod::Boolean(true)]));
+    let mut null = Pod::Null;
+    null.extend(vec![("draft".to_string(), Pod::Boolean(true))]);
+    assert!(null["draft"] == Pod::Boolean(true));
+
+    let mut scalar = Pod::Integer(1);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        scalar.extend(vec![Pod::Integer(2)]);
+    }));
+    assert!(result.is_err(), "should panic on extending an integer");
+    assert!(scalar == Pod::Integer(1));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        array.extend(vec![("k".to_string(), Pod::Null)]);
+    }));
+    assert!(
+        result.is_err(),
+        "should panic on extending an array with pairs"
+    );
+    assert!(array.len() == 3);
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_pod_as_epoch_seconds() -> std::result::Result<(), Error> {
+    let pod = Pod::String("2021-08-01T12:30:00+02:00".to_string());
+    assert_eq!(pod.as_epoch_seconds()?, 1627813800);
+    let pod = Pod::String("2021-08-01T10:30:00Z".to_string());
+    assert_eq!(pod.as_epoch_seconds()?, 1627813800);
+    let pod = Pod::String("2021-08-01 10:30:00".to_string());
+    assert_eq!(pod.as_epoch_seconds()?, 1627813800);
+    let pod = Pod::String("1970-01-02".to_string());
+    assert_eq!(pod.as_epoch_seconds()?, 86400);
+    assert!(Pod::String("yesterday".to_string())
+        .as_epoch_seconds()
+        .is_err());
+    assert!(Pod::Integer(0).as_epoch_seconds() == Err(Error::type_mismatch("String", "integer")));
+    Ok(())
+}
+
+#[test]
+fn test_pod_zip_and_enumerate() -> std::result::Result<(), Error> {
+    let labels = Pod::Array(vec![Pod::String("a".into()), Pod::String("b".into())]);
+    let values = Pod::Array(vec![Pod::Integer(1), Pod::Integer(2)]);
+    let zipped = labels.zip(&values)?;
+    assert!(zipped.len() == 2);
+    assert!(zipped[0] == Pod::Array(vec![Pod::String("a".into()), Pod::Integer(1)]));
+    assert!(zipped[1] == Pod::Array(vec![Pod::String("b".into()), Pod::Integer(2)]));
+    let short = Pod::Array(vec![Pod::Null]);
+    assert!(labels.zip(&short) == Err(Error::length_mismatch(2, 1)));
+    assert!(labels.zip(&Pod::Null) == Err(Error::type_mismatch("Array", "null")));
+
+    let enumerated = labels.enumerate()?;
+    assert!(enumerated[1] == Pod::Array(vec![Pod::Integer(1), Pod::String("b".into())]));
+    assert!(Pod::new_hash().enumerate() == Err(Error::type_mismatch("Array", "hash")));
+    Ok(())
+}
+
+#[test]
+fn test_pod_deserialize_lenient() -> std::result::Result<(), Error> {
+    use serde::Deserialize;
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Config {
+        version: String,
+        ratio: String,
+        draft: String,
+        weight: i64,
+        tags: Vec<String>,
+        author: Option<String>,
+    }
+    let mut pod = Pod::new_hash();
+    pod["version"] = Pod::Integer(2);
+    pod["ratio"] = Pod::Float(0.5);
+    pod["draft"] = Pod::Boolean(true);
+    pod["weight"] = Pod::Integer(3);
+    pod["tags"] = Pod::Array(vec![Pod::Integer(1), Pod::String("two".into())]);
+    pod["author"] = Pod::Null;
+    assert!(
+        pod.deserialize::<Config>().is_err(),
+        "should be strict by default"
+    );
+    let cfg: Config = pod.deserialize_lenient()?;
+    let cfg_expected = Config {
+        version: "2".to_string(),
+        ratio: "0.5".to_string(),
+        draft: "true".to_string(),
+        weight: 3,
+        tags: vec!["1".to_string(), "two".to_string()],
+        author: None,
+    };
+    assert_eq!(cfg, cfg_expected);
+    pod["weight"] = Pod::String("3".into());
+    assert!(pod.deserialize_lenient::<Config>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_pod_deserialize_error_path() -> std::result::Result<(), Error> {
+    use serde::Deserialize;
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Analytics {
+        alexa: Vec<i64>,
+    }
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Config {
+        analytics: Analytics,
+    }
+    let mut pod = Pod::new_hash();
+    pod["analytics"]["alexa"] = Pod::Array(vec![Pod::Integer(1), Pod::String("two".into())]);
+    let err = pod.deserialize_lenient::<Config>().unwrap_err();
+    assert!(
+        matches!(err, Error::DeserializeError(ref msg) if msg.starts_with("at analytics.alexa.1: invalid type")),
+        "{}",
+        err
+    );
+
+    pod["analytics"] = Pod::new_hash();
+    let err = pod.deserialize_lenient::<Config>().unwrap_err();
+    assert_eq!(
+        err,
+        Error::DeserializeError("at analytics: missing field `alexa`".to_string())
+    );
+    assert_eq!(Error::from(pod.deserialize::<Config>().unwrap_err()), err);
+    Ok(())
+}
+
+#[test]
+fn test_pod_flatten() -> std::result::Result<(), Error> {
+    let mut pod = Pod::new_hash();
+    pod["a"]["b"] = Pod::Integer(1);
+    pod["a"]["tags"] = Pod::Array(vec![Pod::String("x".into()), Pod::String("y".into())]);
+    pod["list"] = Pod::Array(vec![Pod::Null, Pod::new_hash()]);
+    pod["list"][0]["name"] = Pod::String("first".into());
+    pod["empty"] = Pod::new_array();
+    pod["top"] = Pod::Boolean(true);
+
+    let flat = pod.flatten();
+    let expected: Pod = vec![
+        ("a.b".to_string(), Pod::Integer(1)),
+        ("a.tags.0".to_string(), Pod::String("x".into())),
+        ("a.tags.1".to_string(), Pod::String("y".into())),
+        ("list.0.name".to_string(), Pod::String("first".into())),
+        ("list.1".to_string(), Pod::new_hash()),
+        ("empty".to_string(), Pod::new_array()),
+        ("top".to_string(), Pod::Boolean(true)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(flat, expected);
+
+    let flat = pod.flatten_with_separator("/");
+    assert!(flat["a/tags/1"] == Pod::String("y".into()));
+    assert!(Pod::Integer(1).flatten()[""] == Pod::Integer(1));
+    Ok(())
+}
+
+#[test]
+fn test_pod_with_capacity() -> std::result::Result<(), Error> {
+    match Pod::array_with_capacity(16) {
+        Pod::Array(vec) => assert!(vec.is_empty() && vec.capacity() >= 16),
+        _ => panic!("should get an array"),
+    }
+    match Pod::hash_with_capacity(16) {
+        Pod::Hash(hash) => assert!(hash.is_empty() && hash.capacity() >= 16),
+        _ => panic!("should get a hash"),
+    }
+    Ok(())
+}
+
 #[test]
 fn test_pod_deserialize() -> std::result::Result<(), Error> {
     use serde::Deserialize;
@@ -491,3 +2579,176 @@ fn test_pod_deserialize() -> std::result::Result<(), Error> {
     assert!(cfg == cfg_expected);
     Ok(())
 }
+
+#[test]
+fn test_pod_merge() -> std::result::Result<(), Error> {
+    let mut a = Pod::new_hash();
+    a["title"] = Pod::String("hello".into());
+    a["nested"]["keep"] = Pod::Boolean(true);
+    a["nested"]["replace"] = Pod::Integer(1);
+    let mut b = Pod::new_hash();
+    b["nested"]["replace"] = Pod::Integer(2);
+    b["extra"] = Pod::Null;
+    a.merge(b);
+    assert!(a["title"] == Pod::String("hello".into()));
+    assert!(a["nested"]["keep"] == Pod::Boolean(true));
+    assert!(a["nested"]["replace"] == Pod::Integer(2));
+    assert!(a["extra"] == Pod::Null);
+    assert!(a.len() == 3);
+    Ok(())
+}
+
+#[test]
+fn test_pod_deserialize_with_env_overrides() -> std::result::Result<(), Error> {
+    use serde::Deserialize;
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Config {
+        title: String,
+        draft: bool,
+        weight: i64,
+    }
+    std::env::set_var("GRAY_MATTER_TEST_ENV_DRAFT", "false");
+    std::env::set_var("GRAY_MATTER_TEST_ENV_WEIGHT", "10");
+    let mut pod = Pod::new_hash();
+    pod["title"] = Pod::String("hello".to_string());
+    pod["draft"] = Pod::Boolean(true);
+    pod["weight"] = Pod::Integer(1);
+    let cfg = pod.deserialize_with_env_overrides::<Config>("GRAY_MATTER_TEST_ENV");
+    std::env::remove_var("GRAY_MATTER_TEST_ENV_DRAFT");
+    std::env::remove_var("GRAY_MATTER_TEST_ENV_WEIGHT");
+    let cfg = cfg?;
+    let cfg_expected = Config {
+        title: "hello".to_string(),
+        draft: false,
+        weight: 10,
+    };
+    assert_eq!(cfg, cfg_expected);
+    Ok(())
+}
+
+#[test]
+fn test_pod_from_prefixed_vars() {
+    let var = |key: &str, value: &str| (OsString::from(key), OsString::from(value));
+    let vars = vec![
+        var("APP_TITLE", "hello"),
+        var("APP_WEIGHT", "10"),
+        var("APP_ZIP", "042"),
+        var("APP_RATIO", "inf"),
+        var("APP_SCORE", "NaN"),
+        var("APP_DRAFT", "false"),
+        var("OTHER_TITLE", "ignored"),
+    ];
+    let pod = Pod::from_prefixed_vars("APP", vars);
+    assert_eq!(pod["title"], Pod::String("hello".into()));
+    assert_eq!(pod["weight"], Pod::Integer(10));
+    assert_eq!(pod["zip"], Pod::String("042".into()));
+    assert_eq!(pod["ratio"], Pod::String("inf".into()));
+    assert_eq!(pod["score"], Pod::String("NaN".into()));
+    assert_eq!(pod["draft"], Pod::Boolean(false));
+    assert_eq!(pod.len(), 6);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_pod_from_prefixed_vars_skips_non_unicode() {
+    use std::os::unix::ffi::OsStringExt;
+    let vars = vec![
+        (
+            OsString::from("APP_TITLE"),
+            OsString::from_vec(vec![0x66, 0xff]),
+        ),
+        (
+            OsString::from_vec(vec![0x41, 0x50, 0x50, 0x5f, 0xff]),
+            OsString::from("x"),
+        ),
+        (OsString::from("APP_DRAFT"), OsString::from("true")),
+    ];
+    let pod = Pod::from_prefixed_vars("APP", vars);
+    assert_eq!(pod["draft"], Pod::Boolean(true));
+    assert_eq!(pod.len(), 1);
+}
+
+#[test]
+fn test_pod_deserialize_deny_unknown_fields() -> std::result::Result<(), Error> {
+    use serde::Deserialize;
+    #[derive(Deserialize, PartialEq, Debug)]
+    #[serde(deny_unknown_fields)]
+    struct Config {
+        title: String,
+    }
+    let mut pod = Pod::new_hash();
+    pod["title"] = Pod::String("hello".to_string());
+    let cfg: Config = pod.deserialize()?;
+    assert_eq!(cfg.title, "hello");
+    pod["extra"] = Pod::Boolean(true);
+    let err = pod.deserialize::<Config>().unwrap_err();
+    assert!(err.to_string().contains("unknown field `extra`"));
+    Ok(())
+}
+
+#[test]
+fn test_pod_deserialize_newtype() -> std::result::Result<(), Error> {
+    use serde::Deserialize;
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Slug(String);
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Weight(i64);
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Tags(Vec<String>);
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Config {
+        slug: Slug,
+        weight: Weight,
+        tags: Tags,
+    }
+    let mut pod = Pod::new_hash();
+    pod["slug"] = Pod::String("hello-world".to_string());
+    pod["weight"] = Pod::Integer(3);
+    pod["tags"] = Pod::Array(vec![Pod::String("a".to_string())]);
+    let cfg_expected = Config {
+        slug: Slug("hello-world".to_string()),
+        weight: Weight(3),
+        tags: Tags(vec!["a".to_string()]),
+    };
+    assert_eq!(pod.deserialize::<Config>()?, cfg_expected);
+    assert_eq!(pod.deserialize_lenient::<Config>()?, cfg_expected);
+    assert_eq!(pod["slug"].deserialize::<Slug>()?, cfg_expected.slug);
+    assert_eq!(pod["weight"].deserialize::<Weight>()?, cfg_expected.weight);
+    assert_eq!(pod["tags"].deserialize::<Tags>()?, cfg_expected.tags);
+    Ok(())
+}
+
+#[test]
+fn test_pod_deserialize_struct_from_array() -> std::result::Result<(), Error> {
+    use serde::Deserialize;
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Point(i64, i64, String);
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Named {
+        x: i64,
+        label: String,
+    }
+    let pod = Pod::Array(vec![
+        Pod::Integer(1),
+        Pod::Integer(2),
+        Pod::String("origin".into()),
+    ]);
+    let expected = Point(1, 2, "origin".to_string());
+    assert_eq!(pod.deserialize::<Point>()?, expected);
+    assert_eq!(pod.deserialize_lenient::<Point>()?, expected);
+
+    let pod = Pod::Array(vec![Pod::Integer(1), Pod::String("origin".into())]);
+    let expected = Named {
+        x: 1,
+        label: "origin".to_string(),
+    };
+    assert_eq!(pod.deserialize::<Named>()?, expected);
+    assert_eq!(pod.deserialize_lenient::<Named>()?, expected);
+
+    let pod = Pod::Array(vec![Pod::String("origin".into()), Pod::Integer(1)]);
+    assert!(
+        pod.deserialize::<Named>().is_err(),
+        "should match fields by order"
+    );
+    Ok(())
+}